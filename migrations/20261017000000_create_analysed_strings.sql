@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS analysed_strings (
+    id                  VARCHAR(64) PRIMARY KEY,
+    value               TEXT NOT NULL UNIQUE,
+    length              INTEGER NOT NULL,
+    is_palindrome       BOOLEAN NOT NULL,
+    unique_char_count   INTEGER NOT NULL,
+    word_count          INTEGER NOT NULL,
+    char_frequency_map  JSONB NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_is_palindrome ON analysed_strings (is_palindrome);
+CREATE INDEX IF NOT EXISTS idx_length ON analysed_strings (length);
+CREATE INDEX IF NOT EXISTS idx_word_count ON analysed_strings (word_count);
+CREATE INDEX IF NOT EXISTS idx_created_at ON analysed_strings (created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_char_frequency_map_gin ON analysed_strings USING GIN (char_frequency_map);
@@ -0,0 +1,16 @@
+-- `length` now holds the grapheme cluster count; byte and code point counts get their own columns.
+-- Existing rows keep their byte-based `length` until they are re-analysed.
+ALTER TABLE analysed_strings
+    ADD COLUMN IF NOT EXISTS byte_length INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS codepoint_length INTEGER NOT NULL DEFAULT 0;
+
+UPDATE analysed_strings
+SET byte_length = octet_length(value),
+    codepoint_length = char_length(value);
+
+ALTER TABLE analysed_strings
+    ALTER COLUMN byte_length DROP DEFAULT,
+    ALTER COLUMN codepoint_length DROP DEFAULT;
+
+CREATE INDEX IF NOT EXISTS idx_byte_length ON analysed_strings (byte_length);
+CREATE INDEX IF NOT EXISTS idx_codepoint_length ON analysed_strings (codepoint_length);
@@ -0,0 +1,18 @@
+-- Loose palindromes ignore everything but letters and digits; normalised palindromes compare
+-- NFKD-normalised grapheme clusters. The normalised column starts as a copy of the strict result
+-- until rows are re-analysed.
+ALTER TABLE analysed_strings
+    ADD COLUMN IF NOT EXISTS is_loose_palindrome BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS is_normalised_palindrome BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE analysed_strings
+SET is_loose_palindrome = regexp_replace(lower(value), '[^[:alnum:]]', '', 'g')
+        = reverse(regexp_replace(lower(value), '[^[:alnum:]]', '', 'g')),
+    is_normalised_palindrome = is_palindrome;
+
+ALTER TABLE analysed_strings
+    ALTER COLUMN is_loose_palindrome DROP DEFAULT,
+    ALTER COLUMN is_normalised_palindrome DROP DEFAULT;
+
+CREATE INDEX IF NOT EXISTS idx_is_loose_palindrome ON analysed_strings (is_loose_palindrome);
+CREATE INDEX IF NOT EXISTS idx_is_normalised_palindrome ON analysed_strings (is_normalised_palindrome);
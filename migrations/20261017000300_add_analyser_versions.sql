@@ -0,0 +1,6 @@
+-- Records which analyser versions produced each row, e.g. {"length": 1, "palindrome": 1}.
+-- Existing rows start empty so every analyser counts as outdated until they are re-analysed.
+ALTER TABLE analysed_strings
+    ADD COLUMN IF NOT EXISTS analyser_versions JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+CREATE INDEX IF NOT EXISTS idx_analyser_versions_gin ON analysed_strings USING GIN (analyser_versions);
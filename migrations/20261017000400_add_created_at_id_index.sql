@@ -0,0 +1,2 @@
+-- Keyset pagination walks `(created_at, id)` in descending order.
+CREATE INDEX IF NOT EXISTS idx_created_at_id ON analysed_strings (created_at DESC, id DESC);
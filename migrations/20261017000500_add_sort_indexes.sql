@@ -0,0 +1,7 @@
+-- Composite indexes so every sortable column can be walked with `(column, id)` keyset cursors.
+CREATE INDEX IF NOT EXISTS idx_length_id ON analysed_strings (length, id);
+CREATE INDEX IF NOT EXISTS idx_byte_length_id ON analysed_strings (byte_length, id);
+CREATE INDEX IF NOT EXISTS idx_codepoint_length_id ON analysed_strings (codepoint_length, id);
+CREATE INDEX IF NOT EXISTS idx_word_count_id ON analysed_strings (word_count, id);
+CREATE INDEX IF NOT EXISTS idx_unique_char_count_id ON analysed_strings (unique_char_count, id);
+CREATE INDEX IF NOT EXISTS idx_value_id ON analysed_strings (value, id);
@@ -0,0 +1,6 @@
+-- Trigram GIN index serves LIKE/ILIKE substring and suffix searches and regex matches;
+-- the text_pattern_ops btree serves case-sensitive prefix searches regardless of collation.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_value_trgm ON analysed_strings USING GIN (value gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_value_pattern ON analysed_strings (value text_pattern_ops);
@@ -0,0 +1,10 @@
+-- Full-text search: the english tsvector is stored as a generated column, the other
+-- supported text search configurations are served by expression indexes.
+ALTER TABLE analysed_strings
+    ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+    GENERATED ALWAYS AS (to_tsvector('english'::regconfig, value)) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_search_vector ON analysed_strings USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_search_vector_french ON analysed_strings USING GIN (to_tsvector('french'::regconfig, value));
+CREATE INDEX IF NOT EXISTS idx_search_vector_spanish ON analysed_strings USING GIN (to_tsvector('spanish'::regconfig, value));
+CREATE INDEX IF NOT EXISTS idx_search_vector_simple ON analysed_strings USING GIN (to_tsvector('simple'::regconfig, value));
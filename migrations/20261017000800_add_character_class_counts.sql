@@ -0,0 +1,29 @@
+-- Per-string counts of each character class. ASCII values are counted here, with the analyser's
+-- Unicode categories spelt out for ASCII; other rows stay NULL, which no filter matches, until
+-- the backfill re-analyses them as their analyser versions lack `character_classes`.
+ALTER TABLE analysed_strings
+    ADD COLUMN IF NOT EXISTS digit_count INTEGER,
+    ADD COLUMN IF NOT EXISTS uppercase_count INTEGER,
+    ADD COLUMN IF NOT EXISTS lowercase_count INTEGER,
+    ADD COLUMN IF NOT EXISTS punctuation_count INTEGER,
+    ADD COLUMN IF NOT EXISTS whitespace_count INTEGER,
+    ADD COLUMN IF NOT EXISTS symbol_count INTEGER,
+    ADD COLUMN IF NOT EXISTS emoji_count INTEGER;
+
+UPDATE analysed_strings
+SET digit_count = regexp_count(value, '[0-9]'),
+    uppercase_count = regexp_count(value, '[A-Z]'),
+    lowercase_count = regexp_count(value, '[a-z]'),
+    punctuation_count = regexp_count(value, '[]!"#%&''()*,./:;?@[\\_{}-]'),
+    whitespace_count = regexp_count(value, '[\t\n\v\f\r ]'),
+    symbol_count = regexp_count(value, '[$+<=>^`|~]'),
+    emoji_count = 0
+WHERE value ~ '^[\x01-\x7F]*$';
+
+CREATE INDEX IF NOT EXISTS idx_digit_count ON analysed_strings (digit_count);
+CREATE INDEX IF NOT EXISTS idx_uppercase_count ON analysed_strings (uppercase_count);
+CREATE INDEX IF NOT EXISTS idx_lowercase_count ON analysed_strings (lowercase_count);
+CREATE INDEX IF NOT EXISTS idx_punctuation_count ON analysed_strings (punctuation_count);
+CREATE INDEX IF NOT EXISTS idx_whitespace_count ON analysed_strings (whitespace_count);
+CREATE INDEX IF NOT EXISTS idx_symbol_count ON analysed_strings (symbol_count);
+CREATE INDEX IF NOT EXISTS idx_emoji_count ON analysed_strings (emoji_count);
@@ -0,0 +1,34 @@
+-- Vowel and consonant counts per string. ASCII values are counted here as the analyser counts
+-- them; other rows, whose accented letters need Unicode decomposition, stay NULL, which no filter
+-- matches, until the backfill re-analyses them as their analyser versions lack `vowels`.
+ALTER TABLE analysed_strings
+    ADD COLUMN IF NOT EXISTS vowel_count INTEGER,
+    ADD COLUMN IF NOT EXISTS consonant_count INTEGER,
+    ADD COLUMN IF NOT EXISTS vowel_ratio DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS longest_consonant_run INTEGER;
+
+UPDATE analysed_strings
+SET vowel_count = letters.vowels,
+    consonant_count = letters.consonants,
+    vowel_ratio = CASE
+        WHEN letters.vowels + letters.consonants = 0 THEN 0
+        ELSE letters.vowels::DOUBLE PRECISION / (letters.vowels + letters.consonants)
+    END,
+    longest_consonant_run = COALESCE(
+        (SELECT max(length(run[1]))
+         FROM regexp_matches(analysed_strings.value, '[b-df-hj-np-tv-z]+', 'gi') AS run),
+        0
+    )
+FROM (
+    SELECT id,
+        regexp_count(value, '[aeiou]', 1, 'i') AS vowels,
+        regexp_count(value, '[b-df-hj-np-tv-z]', 1, 'i') AS consonants
+    FROM analysed_strings
+) AS letters
+WHERE analysed_strings.id = letters.id
+    AND analysed_strings.value ~ '^[\x01-\x7F]*$';
+
+CREATE INDEX IF NOT EXISTS idx_vowel_count ON analysed_strings (vowel_count);
+CREATE INDEX IF NOT EXISTS idx_consonant_count ON analysed_strings (consonant_count);
+CREATE INDEX IF NOT EXISTS idx_vowel_ratio ON analysed_strings (vowel_ratio);
+CREATE INDEX IF NOT EXISTS idx_longest_consonant_run ON analysed_strings (longest_consonant_run);
@@ -9,17 +9,29 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
-    middleware::rate_limiter::rate_limit_middleware,
+    middleware::{admin::require_admin_token, rate_limiter::rate_limit_middleware},
     models::{
-        nlp::{InterpretedQuery, NlpResponse},
-        properties::{AnalysedString, StringProperties},
+        analysers::{AnalyserInfo, AnalysersResponse},
+        backfill::{BackfillProgress, BackfillRequest, BackfillStatus},
+        nlp::{
+            AlternativeInterpretation, Correction, InterpretedFilter, InterpretedQuery,
+            NlpResponse, QueryToken, SuggestResponse, Suggestion, TextSpan, VocabularyResponse,
+        },
+        properties::{AnalysedString, MatchedString, StringProperties},
         requests::CreateStringRequest,
         responses::{ApiErrorResponse, GetStringsResponse},
+        similarity::{SimilarString, SimilarStringsResponse},
         state::AppState,
     },
-    routes::strings::{
-        create_string, delete_string, get_all_strings_wrapper, get_by_natural_language, get_string,
+    routes::{
+        admin::{get_backfill_progress, reload_nlp_vocabulary, start_backfill_job},
+        analysers::get_analysers,
+        strings::{
+            create_string, delete_string, get_all_strings_wrapper, get_by_natural_language,
+            get_nlp_suggestions, get_nlp_vocabulary, get_similar_strings, get_string,
+        },
     },
+    utils::nlp::{Language, LengthAdjective, Vocabulary},
 };
 
 #[derive(OpenApi)]
@@ -29,21 +41,49 @@ use crate::{
         crate::routes::strings::get_string,
         crate::routes::strings::get_all_strings,
         crate::routes::strings::get_by_natural_language,
+        crate::routes::strings::get_nlp_vocabulary,
+        crate::routes::strings::get_nlp_suggestions,
+        crate::routes::strings::get_similar_strings,
         crate::routes::strings::delete_string,
+        crate::routes::analysers::get_analysers,
+        crate::routes::admin::start_backfill_job,
+        crate::routes::admin::get_backfill_progress,
+        crate::routes::admin::reload_nlp_vocabulary,
     ),
     components(
         schemas(
             AnalysedString,
+            MatchedString,
             StringProperties,
             CreateStringRequest,
             GetStringsResponse,
             NlpResponse,
             InterpretedQuery,
+            InterpretedFilter,
+            AlternativeInterpretation,
+            Correction,
+            SuggestResponse,
+            Suggestion,
+            Language,
+            VocabularyResponse,
+            Vocabulary,
+            LengthAdjective,
+            QueryToken,
+            TextSpan,
             ApiErrorResponse,
+            SimilarString,
+            SimilarStringsResponse,
+            AnalyserInfo,
+            AnalysersResponse,
+            BackfillRequest,
+            BackfillProgress,
+            BackfillStatus,
         )
     ),
     tags(
-        (name = "Strings", description = "String analysis API endpoints")
+        (name = "Strings", description = "String analysis API endpoints"),
+        (name = "Analysers", description = "Analyser registry endpoints"),
+        (name = "Admin", description = "Maintenance endpoints (require `Authorization: Bearer <ADMIN_TOKEN>`)")
     ),
     info(
         title = "String Analysis API",
@@ -61,16 +101,36 @@ pub async fn build_app(state: AppState) -> Router {
             "/strings/filter-by-natural-language",
             get(get_by_natural_language),
         )
+        .route(
+            "/strings/filter-by-natural-language/vocabulary",
+            get(get_nlp_vocabulary),
+        )
+        .route(
+            "/strings/filter-by-natural-language/suggest",
+            get(get_nlp_suggestions),
+        )
+        .route("/strings/similar", get(get_similar_strings))
         .route("/strings/{string_value}", get(get_string))
         .route("/strings/{string_value}", delete(delete_string))
+        .route("/analysers", get(get_analysers))
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
             rate_limit_middleware,
         ));
 
+    let admin_routes = Router::new()
+        .route("/admin/backfill", post(start_backfill_job))
+        .route("/admin/backfill", get(get_backfill_progress))
+        .route("/admin/nlp/vocabulary/reload", post(reload_nlp_vocabulary))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            require_admin_token,
+        ));
+
     Router::new()
         .route("/", get(health_check))
         .merge(api_routes)
+        .merge(admin_routes)
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .with_state(state)
 }
@@ -1,6 +1,7 @@
 use redis::{AsyncCommands, RedisError, aio::MultiplexedConnection};
+use serde::{Serialize, de::DeserializeOwned};
 
-use crate::models::properties::AnalysedString;
+use crate::models::{backfill::BackfillProgress, properties::AnalysedString};
 
 #[derive(Clone)]
 pub struct CacheService {
@@ -22,13 +23,8 @@ impl CacheService {
 
         let result: Option<String> = conn.get(&cache_key).await?;
 
-        match result {
-            Some(json_data) => {
-                let analysed_string: AnalysedString = serde_json::from_str(&json_data).unwrap();
-                Ok(Some(analysed_string))
-            }
-            None => Ok(None),
-        }
+        // An entry written in an older format is a miss; the caller reloads and rewrites it.
+        Ok(result.and_then(|json_data| serde_json::from_str(&json_data).ok()))
     }
 
     pub async fn set(&self, analysed_data: &AnalysedString) -> Result<(), RedisError> {
@@ -40,6 +36,95 @@ impl CacheService {
         Ok(())
     }
 
+    /// Overwrites an existing `string:{id}` entry, keeping its TTL. Missing keys are left absent.
+    pub async fn refresh(&self, analysed_data: &AnalysedString) -> Result<(), RedisError> {
+        let cache_key = format!("string:{}", analysed_data.id);
+        let json_data = serde_json::to_string(analysed_data).unwrap();
+        let mut conn = self.redis.clone();
+
+        let _: () = redis::cmd("SET")
+            .arg(&cache_key)
+            .arg(json_data)
+            .arg("XX")
+            .arg("KEEPTTL")
+            .query_async(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Reads a cached query result stored under `query:{key}`.
+    pub async fn get_query<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, RedisError> {
+        let cache_key = format!("query:{}", key);
+        let mut conn = self.redis.clone();
+
+        let result: Option<String> = conn.get(&cache_key).await?;
+
+        Ok(result.and_then(|json_data| serde_json::from_str(&json_data).ok()))
+    }
+
+    /// Caches a query result for 15 minutes; every write clears it through `invalidate`.
+    pub async fn set_query<T: Serialize>(&self, key: &str, result: &T) -> Result<(), RedisError> {
+        let cache_key = format!("query:{}", key);
+        let json_data = serde_json::to_string(result).unwrap();
+        let mut conn = self.redis.clone();
+
+        let _: () = conn.set_ex(&cache_key, json_data, 900).await?;
+        Ok(())
+    }
+
+    pub async fn get_backfill_progress(&self) -> Result<Option<BackfillProgress>, RedisError> {
+        let result = self.get_backfill_progress_json().await?;
+
+        Ok(result.and_then(|json_data| serde_json::from_str(&json_data).ok()))
+    }
+
+    /// The progress exactly as stored, to pass back to `replace_backfill_progress`.
+    pub async fn get_backfill_progress_json(&self) -> Result<Option<String>, RedisError> {
+        let mut conn = self.redis.clone();
+
+        conn.get("backfill:progress").await
+    }
+
+    /// Stores `progress` only if the stored progress is still `expected`, checking and
+    /// writing in one script. Returns `false` when another writer changed it in between.
+    pub async fn replace_backfill_progress(
+        &self,
+        expected: Option<&str>,
+        progress: &BackfillProgress,
+    ) -> Result<bool, RedisError> {
+        let json_data = serde_json::to_string(progress).unwrap();
+        let mut conn = self.redis.clone();
+
+        let script = r#"
+            local current = redis.call('GET', KEYS[1])
+            if (current or '') ~= ARGV[1] then
+                return 0
+            end
+            redis.call('SET', KEYS[1], ARGV[2])
+            return 1
+        "#;
+
+        let replaced: i32 = redis::Script::new(script)
+            .key("backfill:progress")
+            .arg(expected.unwrap_or_default())
+            .arg(json_data)
+            .invoke_async(&mut conn)
+            .await?;
+
+        Ok(replaced == 1)
+    }
+
+    pub async fn set_backfill_progress(
+        &self,
+        progress: &BackfillProgress,
+    ) -> Result<(), RedisError> {
+        let json_data = serde_json::to_string(progress).unwrap();
+        let mut conn = self.redis.clone();
+
+        let _: () = conn.set("backfill:progress", json_data).await?;
+        Ok(())
+    }
+
     pub async fn delete(&self, id: &str) -> Result<(), RedisError> {
         let cache_key = format!("string:{}", id);
         let mut conn = self.redis.clone();
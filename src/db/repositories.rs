@@ -1,16 +1,139 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, SecondsFormat, Utc};
-use sqlx::{Error, QueryBuilder, Result, query};
+use sqlx::{Error, Executor, FromRow, Postgres, QueryBuilder, Result, query, query_as};
 
 use crate::{
     db::pool::DbPool,
     models::{
-        filters::StringFilters,
-        properties::{AnalysedString, StringProperties},
+        expression::{Condition, FilterExpression},
+        filters::{SearchLanguage, StringFilters, character_set, escape_like},
+        pagination::{CursorKey, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT, PageCursor, StringPage},
+        properties::{AnalysedString, MatchedString, StringProperties},
     },
 };
 
+/// Bounds how long a regex filter may keep a connection busy.
+const REGEX_STATEMENT_TIMEOUT: &str = "SELECT set_config('statement_timeout', '2s', true)";
+
+/// `ts_headline` options for search snippets: up to two fragments, matches wrapped in `<mark>`.
+const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=20, MinWords=5";
+
+const SELECT_COLUMNS: &str = "id, value, length, byte_length, codepoint_length, is_palindrome, is_loose_palindrome, is_normalised_palindrome, unique_char_count, word_count, digit_count, uppercase_count, lowercase_count, punctuation_count, whitespace_count, symbol_count, emoji_count, vowel_count, consonant_count, vowel_ratio, longest_consonant_run, char_frequency_map, analyser_versions, created_at";
+
+/// Column-for-column image of an `analysed_strings` row. The character class and vowel counts
+/// are `NULL` on rows migrated before they existed until the backfill re-analyses them.
+#[derive(FromRow)]
+struct AnalysedStringRow {
+    id: String,
+    value: String,
+    length: i32,
+    byte_length: i32,
+    codepoint_length: i32,
+    is_palindrome: bool,
+    is_loose_palindrome: bool,
+    is_normalised_palindrome: bool,
+    unique_char_count: i32,
+    word_count: i32,
+    digit_count: Option<i32>,
+    uppercase_count: Option<i32>,
+    lowercase_count: Option<i32>,
+    punctuation_count: Option<i32>,
+    whitespace_count: Option<i32>,
+    symbol_count: Option<i32>,
+    emoji_count: Option<i32>,
+    vowel_count: Option<i32>,
+    consonant_count: Option<i32>,
+    vowel_ratio: Option<f64>,
+    longest_consonant_run: Option<i32>,
+    char_frequency_map: serde_json::Value,
+    analyser_versions: serde_json::Value,
+    created_at: DateTime<Utc>,
+}
+
+/// A listed row with its full-text search rank and snippet, `NULL` unless `q` was given.
+#[derive(FromRow)]
+struct MatchedStringRow {
+    #[sqlx(flatten)]
+    row: AnalysedStringRow,
+    rank: Option<f32>,
+    snippet: Option<String>,
+}
+
+#[derive(FromRow)]
+struct SimilarStringRow {
+    #[sqlx(flatten)]
+    row: AnalysedStringRow,
+    similarity: f32,
+}
+
+impl MatchedStringRow {
+    /// Value of `column` in this row, as stored in a keyset cursor.
+    fn sort_key(&self, column: &str) -> CursorKey {
+        let row = &self.row;
+        match column {
+            "length" => CursorKey::Integer(row.length),
+            "byte_length" => CursorKey::Integer(row.byte_length),
+            "codepoint_length" => CursorKey::Integer(row.codepoint_length),
+            "word_count" => CursorKey::Integer(row.word_count),
+            "unique_char_count" => CursorKey::Integer(row.unique_char_count),
+            "value" => CursorKey::Text(row.value.clone()),
+            "rank" => CursorKey::Real(self.rank.unwrap_or_default()),
+            _ => CursorKey::timestamp(row.created_at),
+        }
+    }
+}
+
+impl From<MatchedStringRow> for MatchedString {
+    fn from(matched: MatchedStringRow) -> Self {
+        MatchedString {
+            analysed_string: AnalysedString::from(matched.row),
+            rank: matched.rank,
+            snippet: matched.snippet,
+        }
+    }
+}
+
+impl From<AnalysedStringRow> for AnalysedString {
+    fn from(row: AnalysedStringRow) -> Self {
+        let char_frequency_map: HashMap<String, i32> =
+            serde_json::from_value(row.char_frequency_map).unwrap();
+        let analyser_versions: HashMap<String, i32> =
+            serde_json::from_value(row.analyser_versions).unwrap_or_default();
+
+        AnalysedString {
+            id: row.id.clone(),
+            value: row.value,
+            properties: StringProperties {
+                length: row.length,
+                byte_length: row.byte_length,
+                codepoint_length: row.codepoint_length,
+                is_palindrome: row.is_palindrome,
+                is_loose_palindrome: row.is_loose_palindrome,
+                is_normalised_palindrome: row.is_normalised_palindrome,
+                unique_characters: row.unique_char_count,
+                word_count: row.word_count,
+                digit_count: row.digit_count.unwrap_or_default(),
+                uppercase_count: row.uppercase_count.unwrap_or_default(),
+                lowercase_count: row.lowercase_count.unwrap_or_default(),
+                punctuation_count: row.punctuation_count.unwrap_or_default(),
+                whitespace_count: row.whitespace_count.unwrap_or_default(),
+                symbol_count: row.symbol_count.unwrap_or_default(),
+                emoji_count: row.emoji_count.unwrap_or_default(),
+                vowel_count: row.vowel_count.unwrap_or_default(),
+                consonant_count: row.consonant_count.unwrap_or_default(),
+                vowel_ratio: row.vowel_ratio.unwrap_or_default(),
+                longest_consonant_run: row.longest_consonant_run.unwrap_or_default(),
+                sha256_hash: row.id,
+                character_frequency_map: char_frequency_map,
+                analyser_versions,
+            },
+            created_at: row.created_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct StringRepository {
     pool: DbPool,
@@ -24,16 +147,32 @@ impl StringRepository {
     pub async fn create(&self, analysed_string_data: &AnalysedString) -> Result<(), Error> {
         query!(
             r#"
-            INSERT INTO analysed_strings (id, value, length, is_palindrome, unique_char_count, word_count, char_frequency_map, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO analysed_strings (id, value, length, byte_length, codepoint_length, is_palindrome, is_loose_palindrome, is_normalised_palindrome, unique_char_count, word_count, digit_count, uppercase_count, lowercase_count, punctuation_count, whitespace_count, symbol_count, emoji_count, vowel_count, consonant_count, vowel_ratio, longest_consonant_run, char_frequency_map, analyser_versions, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24)
             "#,
             analysed_string_data.id,
             analysed_string_data.value,
             analysed_string_data.properties.length,
+            analysed_string_data.properties.byte_length,
+            analysed_string_data.properties.codepoint_length,
             analysed_string_data.properties.is_palindrome,
+            analysed_string_data.properties.is_loose_palindrome,
+            analysed_string_data.properties.is_normalised_palindrome,
             analysed_string_data.properties.unique_characters,
             analysed_string_data.properties.word_count,
+            analysed_string_data.properties.digit_count,
+            analysed_string_data.properties.uppercase_count,
+            analysed_string_data.properties.lowercase_count,
+            analysed_string_data.properties.punctuation_count,
+            analysed_string_data.properties.whitespace_count,
+            analysed_string_data.properties.symbol_count,
+            analysed_string_data.properties.emoji_count,
+            analysed_string_data.properties.vowel_count,
+            analysed_string_data.properties.consonant_count,
+            analysed_string_data.properties.vowel_ratio,
+            analysed_string_data.properties.longest_consonant_run,
             serde_json::to_value(&analysed_string_data.properties.character_frequency_map).unwrap(),
+            serde_json::to_value(&analysed_string_data.properties.analyser_versions).unwrap(),
             analysed_string_data.created_at.parse::<DateTime<Utc>>().unwrap()
         ).execute(&self.pool)
         .await?;
@@ -53,9 +192,10 @@ impl StringRepository {
     }
 
     pub async fn get_by_value(&self, value: &str) -> Result<Option<AnalysedString>, Error> {
-        let result = query!(
+        let result = query_as!(
+            AnalysedStringRow,
             r#"
-            SELECT id, value, length, is_palindrome, unique_char_count, word_count, char_frequency_map, created_at
+            SELECT id, value, length, byte_length, codepoint_length, is_palindrome, is_loose_palindrome, is_normalised_palindrome, unique_char_count, word_count, digit_count, uppercase_count, lowercase_count, punctuation_count, whitespace_count, symbol_count, emoji_count, vowel_count, consonant_count, vowel_ratio, longest_consonant_run, char_frequency_map, analyser_versions, created_at
             FROM analysed_strings
             WHERE value = $1
             "#,
@@ -63,103 +203,325 @@ impl StringRepository {
         ).fetch_optional(&self.pool)
         .await?;
 
-        match result {
-            Some(row) => {
-                let char_frequency_map: HashMap<String, i32> =
-                    serde_json::from_value(row.char_frequency_map).unwrap();
-
-                Ok(Some(AnalysedString {
-                    id: row.id.clone(),
-                    value: row.value,
-                    properties: StringProperties {
-                        length: row.length,
-                        is_palindrome: row.is_palindrome,
-                        unique_characters: row.unique_char_count,
-                        word_count: row.word_count,
-                        sha256_hash: row.id,
-                        character_frequency_map: char_frequency_map,
-                    },
-                    created_at: row.created_at.to_rfc3339_opts(SecondsFormat::Millis, true),
-                }))
-            }
-
-            None => Ok(None),
-        }
+        Ok(result.map(AnalysedString::from))
     }
 
+    /// Returns one page of rows matching the filters in the requested sort order, with `id`
+    /// breaking ties. `cursor` is the decoded `next_cursor` of the previous page.
     pub async fn filter(
         &self,
         filter_values: &StringFilters,
-    ) -> Result<Vec<AnalysedString>, Error> {
-        let mut query = QueryBuilder::new(
-            "SELECT id, value, length, is_palindrome, unique_char_count, word_count, char_frequency_map, created_at FROM analysed_strings WHERE 1=1",
-        );
+        cursor: Option<&PageCursor>,
+    ) -> Result<StringPage, Error> {
+        let returned = cursor.map_or(0, |cursor| cursor.returned);
+        let remaining = filter_values
+            .max_results
+            .map(|max_results| (max_results - returned).max(0));
 
-        if let Some(is_palindrome) = filter_values.is_palindrome {
-            query.push(" AND is_palindrome = ");
-            query.push_bind(is_palindrome);
-        }
+        let limit = filter_values
+            .limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+            .min(remaining.unwrap_or(i64::MAX));
+
+        let mut query = QueryBuilder::new(format!("SELECT {}, ", SELECT_COLUMNS));
+
+        match filter_values.q {
+            Some(ref q) => {
+                let language = filter_values.language.unwrap_or_default();
 
-        if let Some(min_length) = filter_values.min_length {
-            query.push(" AND length >= ");
-            query.push_bind(min_length);
+                push_rank(&mut query, filter_values);
+                query.push(format!(
+                    " AS rank, ts_headline({}, value, ",
+                    language.config()
+                ));
+                push_tsquery(&mut query, language, q);
+                query.push(format!(", '{}') AS snippet", HEADLINE_OPTIONS));
+            }
+            None => {
+                query.push("NULL::real AS rank, NULL::text AS snippet");
+            }
         }
 
-        if let Some(max_length) = filter_values.max_length {
-            query.push(" AND length <= ");
-            query.push_bind(max_length);
+        query.push(" FROM analysed_strings WHERE 1=1");
+
+        push_filter_conditions(&mut query, filter_values);
+
+        let sort_column = filter_values.sort_column();
+        let sort_order = filter_values.sort_order();
+
+        if let Some(cursor) = cursor {
+            query.push(" AND (");
+            if sort_column == "rank" {
+                push_rank(&mut query, filter_values);
+            } else {
+                query.push(sort_column);
+            }
+            query.push(format!(", id) {} (", sort_order.keyset_operator()));
+            match cursor.key {
+                CursorKey::Timestamp(micros) => {
+                    query.push_bind(DateTime::from_timestamp_micros(micros).unwrap_or_default())
+                }
+                CursorKey::Integer(value) => query.push_bind(value),
+                CursorKey::Text(ref value) => query.push_bind(value.clone()),
+                CursorKey::Real(rank) => query.push_bind(rank),
+            };
+            query.push(", ");
+            query.push_bind(cursor.id.clone());
+            query.push(")");
         }
 
-        if let Some(word_count) = filter_values.word_count {
-            query.push(" AND word_count = ");
-            query.push_bind(word_count);
+        query.push(format!(
+            " ORDER BY {} {}, id {} LIMIT ",
+            sort_column,
+            sort_order.sql(),
+            sort_order.sql()
+        ));
+        query.push_bind(limit + 1);
+
+        let mut transaction = self.pool.begin().await?;
+
+        if filter_values.regex.is_some() {
+            sqlx::query(REGEX_STATEMENT_TIMEOUT)
+                .execute(&mut *transaction)
+                .await?;
         }
 
-        if let Some(ref contains_char) = filter_values.contains_character {
-            if let Some(first_char) = contains_char.chars().next() {
-                query.push(" AND char_frequency_map ? ");
-                query.push_bind(first_char.to_lowercase().to_string());
+        let mut rows = query
+            .build_query_as::<MatchedStringRow>()
+            .fetch_all(&mut *transaction)
+            .await?;
+
+        let has_more =
+            rows.len() as i64 > limit && remaining.is_none_or(|remaining| remaining > limit);
+        rows.truncate(limit as usize);
+
+        let next_cursor = match rows.last() {
+            Some(last) if has_more => Some(PageCursor {
+                column: sort_column.to_string(),
+                order: sort_order,
+                key: last.sort_key(sort_column),
+                id: last.row.id.clone(),
+                returned: returned + rows.len() as i64,
+            }),
+            _ => None,
+        };
+
+        let total = if filter_values.include_total.unwrap_or(false) {
+            let total = count_matching(&mut *transaction, filter_values).await?;
+            Some(
+                filter_values
+                    .max_results
+                    .map_or(total, |max| total.min(max)),
+            )
+        } else {
+            None
+        };
+
+        transaction.commit().await?;
+
+        Ok(StringPage {
+            data: rows.into_iter().map(MatchedString::from).collect(),
+            next_cursor,
+            has_more,
+            total,
+        })
+    }
+
+    /// Rows whose trigram similarity to `value` is at least `threshold`, most similar first.
+    pub async fn find_similar(
+        &self,
+        value: &str,
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<(AnalysedString, f32)>, Error> {
+        let mut transaction = self.pool.begin().await?;
+
+        // `%` only uses the trigram index against the session threshold, not a bound value.
+        sqlx::query("SELECT set_config('pg_trgm.similarity_threshold', $1, true)")
+            .bind(threshold.to_string())
+            .execute(&mut *transaction)
+            .await?;
+
+        let rows = sqlx::query_as::<_, SimilarStringRow>(&format!(
+            "SELECT {}, similarity(value, $1) AS similarity FROM analysed_strings WHERE value % $1 ORDER BY similarity DESC, id LIMIT $2",
+            SELECT_COLUMNS
+        ))
+        .bind(value)
+        .bind(limit)
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|similar| (AnalysedString::from(similar.row), similar.similarity))
+            .collect())
+    }
+
+    /// Counts every row matching the filters, ignoring pagination.
+    pub async fn count(&self, filter_values: &StringFilters) -> Result<i64, Error> {
+        let mut connection = self.pool.acquire().await?;
+
+        count_matching(&mut *connection, filter_values).await
+    }
+
+    /// Counts rows whose `analyser_versions` do not contain every given analyser version.
+    pub async fn count_outdated(&self, versions: &HashMap<String, i32>) -> Result<i64, Error> {
+        let result = query!(
+            r#"SELECT COUNT(*) as "count!" FROM analysed_strings WHERE NOT (analyser_versions @> $1)"#,
+            serde_json::to_value(versions).unwrap()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result.count)
+    }
+
+    /// Counts the rows a backfill would visit: outdated rows only, unless `versions` is `None`.
+    pub async fn count_for_backfill(
+        &self,
+        versions: Option<&HashMap<String, i32>>,
+    ) -> Result<i64, Error> {
+        match versions {
+            Some(versions) => self.count_outdated(versions).await,
+            None => {
+                let result = query!(r#"SELECT COUNT(*) as "count!" FROM analysed_strings"#)
+                    .fetch_one(&self.pool)
+                    .await?;
+
+                Ok(result.count)
             }
         }
+    }
 
-        query.push(" ORDER BY created_at DESC");
+    /// Fetches the next page of rows ordered by `id`, starting after `after_id`. When
+    /// `versions` is given, only rows computed by a different analyser version are returned.
+    pub async fn page_for_backfill(
+        &self,
+        after_id: Option<&str>,
+        limit: i64,
+        versions: Option<&HashMap<String, i32>>,
+    ) -> Result<Vec<AnalysedString>, Error> {
+        let mut query = QueryBuilder::new(format!(
+            "SELECT {} FROM analysed_strings WHERE 1=1",
+            SELECT_COLUMNS
+        ));
+
+        if let Some(after_id) = after_id {
+            query.push(" AND id > ");
+            query.push_bind(after_id.to_string());
+        }
+
+        if let Some(versions) = versions {
+            query.push(" AND NOT (analyser_versions @> ");
+            query.push_bind(serde_json::to_value(versions).unwrap());
+            query.push(")");
+        }
+
+        query.push(" ORDER BY id LIMIT ");
+        query.push_bind(limit);
 
         let rows = query
-            .build_query_as::<(
-                String,
-                String,
-                i32,
-                bool,
-                i32,
-                i32,
-                serde_json::Value,
-                chrono::DateTime<chrono::Utc>,
-            )>()
+            .build_query_as::<AnalysedStringRow>()
             .fetch_all(&self.pool)
             .await?;
 
-        let results = rows
-            .into_iter()
-            .map(|row| {
-                let char_frequency = serde_json::from_value(row.6).unwrap();
-
-                AnalysedString {
-                    id: row.0.clone(),
-                    value: row.1,
-                    properties: StringProperties {
-                        length: row.2,
-                        is_palindrome: row.3,
-                        unique_characters: row.4,
-                        word_count: row.5,
-                        sha256_hash: row.0,
-                        character_frequency_map: char_frequency,
-                    },
-                    created_at: row.7.to_rfc3339_opts(SecondsFormat::Millis, true),
-                }
-            })
-            .collect();
+        Ok(rows.into_iter().map(AnalysedString::from).collect())
+    }
+
+    /// Overwrites the computed properties of existing rows in a single statement.
+    pub async fn update_properties(
+        &self,
+        analysed_strings: &[AnalysedString],
+    ) -> Result<u64, Error> {
+        let mut ids = Vec::with_capacity(analysed_strings.len());
+        let mut lengths = Vec::with_capacity(analysed_strings.len());
+        let mut byte_lengths = Vec::with_capacity(analysed_strings.len());
+        let mut codepoint_lengths = Vec::with_capacity(analysed_strings.len());
+        let mut palindromes = Vec::with_capacity(analysed_strings.len());
+        let mut loose_palindromes = Vec::with_capacity(analysed_strings.len());
+        let mut normalised_palindromes = Vec::with_capacity(analysed_strings.len());
+        let mut unique_char_counts = Vec::with_capacity(analysed_strings.len());
+        let mut word_counts = Vec::with_capacity(analysed_strings.len());
+        let mut digit_counts = Vec::with_capacity(analysed_strings.len());
+        let mut uppercase_counts = Vec::with_capacity(analysed_strings.len());
+        let mut lowercase_counts = Vec::with_capacity(analysed_strings.len());
+        let mut punctuation_counts = Vec::with_capacity(analysed_strings.len());
+        let mut whitespace_counts = Vec::with_capacity(analysed_strings.len());
+        let mut symbol_counts = Vec::with_capacity(analysed_strings.len());
+        let mut emoji_counts = Vec::with_capacity(analysed_strings.len());
+        let mut vowel_counts = Vec::with_capacity(analysed_strings.len());
+        let mut consonant_counts = Vec::with_capacity(analysed_strings.len());
+        let mut vowel_ratios = Vec::with_capacity(analysed_strings.len());
+        let mut longest_consonant_runs = Vec::with_capacity(analysed_strings.len());
+        let mut char_frequency_maps = Vec::with_capacity(analysed_strings.len());
+        let mut analyser_versions = Vec::with_capacity(analysed_strings.len());
+
+        for analysed_string in analysed_strings {
+            let properties = &analysed_string.properties;
+
+            ids.push(analysed_string.id.clone());
+            lengths.push(properties.length);
+            byte_lengths.push(properties.byte_length);
+            codepoint_lengths.push(properties.codepoint_length);
+            palindromes.push(properties.is_palindrome);
+            loose_palindromes.push(properties.is_loose_palindrome);
+            normalised_palindromes.push(properties.is_normalised_palindrome);
+            unique_char_counts.push(properties.unique_characters);
+            word_counts.push(properties.word_count);
+            digit_counts.push(properties.digit_count);
+            uppercase_counts.push(properties.uppercase_count);
+            lowercase_counts.push(properties.lowercase_count);
+            punctuation_counts.push(properties.punctuation_count);
+            whitespace_counts.push(properties.whitespace_count);
+            symbol_counts.push(properties.symbol_count);
+            emoji_counts.push(properties.emoji_count);
+            vowel_counts.push(properties.vowel_count);
+            consonant_counts.push(properties.consonant_count);
+            vowel_ratios.push(properties.vowel_ratio);
+            longest_consonant_runs.push(properties.longest_consonant_run);
+            char_frequency_maps
+                .push(serde_json::to_value(&properties.character_frequency_map).unwrap());
+            analyser_versions.push(serde_json::to_value(&properties.analyser_versions).unwrap());
+        }
+
+        let result = query!(
+            r#"
+            UPDATE analysed_strings AS s
+            SET length = u.length, byte_length = u.byte_length, codepoint_length = u.codepoint_length, is_palindrome = u.is_palindrome, is_loose_palindrome = u.is_loose_palindrome, is_normalised_palindrome = u.is_normalised_palindrome, unique_char_count = u.unique_char_count, word_count = u.word_count, digit_count = u.digit_count, uppercase_count = u.uppercase_count, lowercase_count = u.lowercase_count, punctuation_count = u.punctuation_count, whitespace_count = u.whitespace_count, symbol_count = u.symbol_count, emoji_count = u.emoji_count, vowel_count = u.vowel_count, consonant_count = u.consonant_count, vowel_ratio = u.vowel_ratio, longest_consonant_run = u.longest_consonant_run, char_frequency_map = u.char_frequency_map, analyser_versions = u.analyser_versions
+            FROM UNNEST($1::varchar[], $2::int4[], $3::int4[], $4::int4[], $5::bool[], $6::bool[], $7::bool[], $8::int4[], $9::int4[], $10::int4[], $11::int4[], $12::int4[], $13::int4[], $14::int4[], $15::int4[], $16::int4[], $17::int4[], $18::int4[], $19::float8[], $20::int4[], $21::jsonb[], $22::jsonb[])
+                AS u(id, length, byte_length, codepoint_length, is_palindrome, is_loose_palindrome, is_normalised_palindrome, unique_char_count, word_count, digit_count, uppercase_count, lowercase_count, punctuation_count, whitespace_count, symbol_count, emoji_count, vowel_count, consonant_count, vowel_ratio, longest_consonant_run, char_frequency_map, analyser_versions)
+            WHERE s.id = u.id
+            "#,
+            &ids,
+            &lengths,
+            &byte_lengths,
+            &codepoint_lengths,
+            &palindromes,
+            &loose_palindromes,
+            &normalised_palindromes,
+            &unique_char_counts,
+            &word_counts,
+            &digit_counts,
+            &uppercase_counts,
+            &lowercase_counts,
+            &punctuation_counts,
+            &whitespace_counts,
+            &symbol_counts,
+            &emoji_counts,
+            &vowel_counts,
+            &consonant_counts,
+            &vowel_ratios,
+            &longest_consonant_runs,
+            &char_frequency_maps,
+            &analyser_versions
+        )
+        .execute(&self.pool)
+        .await?;
 
-        Ok(results)
+        Ok(result.rows_affected())
     }
 
     pub async fn delete_by_value(&self, value: &str) -> Result<bool, Error> {
@@ -170,3 +532,233 @@ impl StringRepository {
         Ok(result.rows_affected() > 0)
     }
 }
+
+async fn count_matching<'c, E>(executor: E, filter_values: &StringFilters) -> Result<i64, Error>
+where
+    E: Executor<'c, Database = Postgres>,
+{
+    let mut query = QueryBuilder::new("SELECT COUNT(*) FROM analysed_strings WHERE 1=1");
+
+    push_filter_conditions(&mut query, filter_values);
+
+    let (count,) = query.build_query_as::<(i64,)>().fetch_one(executor).await?;
+
+    Ok(count)
+}
+
+fn push_filter_conditions(query: &mut QueryBuilder<'_, Postgres>, filter_values: &StringFilters) {
+    if let Some(is_palindrome) = filter_values.is_palindrome {
+        let palindrome_column = filter_values.palindrome_mode.unwrap_or_default().column();
+        query.push(format!(" AND {} = ", palindrome_column));
+        query.push_bind(is_palindrome);
+    }
+
+    for range in filter_values.numeric_ranges() {
+        if let Some(exact) = range.exact {
+            query.push(format!(" AND {} = ", range.column));
+            query.push_bind(exact);
+        }
+
+        if let Some(min) = range.min {
+            query.push(format!(" AND {} >= ", range.column));
+            query.push_bind(min);
+        }
+
+        if let Some(max) = range.max {
+            query.push(format!(" AND {} <= ", range.column));
+            query.push_bind(max);
+        }
+    }
+
+    if let Some(min_vowel_ratio) = filter_values.min_vowel_ratio {
+        query.push(" AND vowel_ratio >= ");
+        query.push_bind(min_vowel_ratio);
+    }
+
+    if let Some(max_vowel_ratio) = filter_values.max_vowel_ratio {
+        query.push(" AND vowel_ratio <= ");
+        query.push_bind(max_vowel_ratio);
+    }
+
+    if let Some(ref contains_char) = filter_values.contains_character
+        && let Some(first_char) = contains_char.chars().next()
+    {
+        query.push(" AND char_frequency_map ? ");
+        query.push_bind(first_char.to_lowercase().to_string());
+    }
+
+    let contains_all = character_set(filter_values.contains_all.as_deref());
+    if !contains_all.is_empty() {
+        query.push(" AND char_frequency_map ?& ");
+        query.push_bind(contains_all);
+    }
+
+    let contains_any = character_set(filter_values.contains_any.as_deref());
+    if !contains_any.is_empty() {
+        query.push(" AND char_frequency_map ?| ");
+        query.push_bind(contains_any);
+    }
+
+    let excludes = character_set(filter_values.excludes.as_deref());
+    if !excludes.is_empty() {
+        query.push(" AND NOT (char_frequency_map ?| ");
+        query.push_bind(excludes);
+        query.push(")");
+    }
+
+    let char_count_bounds = [
+        (&filter_values.min_char_count, ">="),
+        (&filter_values.max_char_count, "<="),
+    ];
+
+    for (char_counts, operator) in char_count_bounds {
+        for (character, count) in char_counts.iter().flat_map(|counts| counts.0.iter()) {
+            query.push(" AND COALESCE((char_frequency_map ->> ");
+            query.push_bind(character.clone());
+            query.push(format!(")::int, 0) {} ", operator));
+            query.push_bind(*count);
+        }
+    }
+
+    if let Some(created_after) = filter_values.created_after {
+        query.push(" AND created_at >= ");
+        query.push_bind(created_after.0);
+    }
+
+    if let Some(created_before) = filter_values.created_before {
+        query.push(" AND created_at < ");
+        query.push_bind(created_before.0);
+    }
+
+    let case_insensitive = filter_values.case_insensitive.unwrap_or(false);
+    let like = if case_insensitive { "ILIKE" } else { "LIKE" };
+
+    let like_patterns = [
+        filter_values
+            .starts_with
+            .as_deref()
+            .map(|prefix| format!("{}%", escape_like(prefix))),
+        filter_values
+            .ends_with
+            .as_deref()
+            .map(|suffix| format!("%{}", escape_like(suffix))),
+        filter_values
+            .contains_substring
+            .as_deref()
+            .map(|substring| format!("%{}%", escape_like(substring))),
+    ];
+
+    for pattern in like_patterns.into_iter().flatten() {
+        query.push(format!(" AND value {} ", like));
+        query.push_bind(pattern);
+        query.push(" ESCAPE '\\'");
+    }
+
+    if let Some(ref regex) = filter_values.regex {
+        query.push(if case_insensitive {
+            " AND value ~* "
+        } else {
+            " AND value ~ "
+        });
+        query.push_bind(regex.clone());
+    }
+
+    if let Some(ref q) = filter_values.q {
+        let language = filter_values.language.unwrap_or_default();
+        query.push(format!(" AND {} @@ ", language.vector()));
+        push_tsquery(query, language, q);
+    }
+
+    if let Some(ref expression) = filter_values.expression {
+        query.push(" AND ");
+        push_expression(query, expression);
+    }
+}
+
+/// Translates a parsed `filter` expression into a parenthesised SQL condition.
+fn push_expression(query: &mut QueryBuilder<'_, Postgres>, expression: &FilterExpression) {
+    match expression {
+        FilterExpression::And(left, right) | FilterExpression::Or(left, right) => {
+            let operator = match expression {
+                FilterExpression::And(..) => " AND ",
+                _ => " OR ",
+            };
+            query.push("(");
+            push_expression(query, left);
+            query.push(operator);
+            push_expression(query, right);
+            query.push(")");
+        }
+        FilterExpression::Not(inner) => {
+            query.push("NOT ");
+            push_expression(query, inner);
+        }
+        FilterExpression::Condition(condition) => {
+            query.push("(");
+            match condition {
+                Condition::Palindrome { mode, value } => {
+                    query.push(format!("{} = ", mode.column()));
+                    query.push_bind(*value);
+                }
+                Condition::Numeric {
+                    column,
+                    comparison,
+                    value,
+                } => {
+                    query.push(format!("{} {} ", column, comparison.sql()));
+                    query.push_bind(*value);
+                }
+                Condition::Ratio {
+                    column,
+                    comparison,
+                    value,
+                } => {
+                    query.push(format!("{} {} ", column, comparison.sql()));
+                    query.push_bind(*value);
+                }
+                Condition::CreatedAt { comparison, value } => {
+                    query.push(format!("created_at {} ", comparison.sql()));
+                    query.push_bind(value.0);
+                }
+                Condition::Contains(characters) => {
+                    query.push("char_frequency_map ?& ");
+                    query.push_bind(characters.clone());
+                }
+                Condition::StartsWith(prefix) => {
+                    query.push("value LIKE ");
+                    query.push_bind(format!("{}%", escape_like(prefix)));
+                    query.push(" ESCAPE '\\'");
+                }
+                Condition::EndsWith(suffix) => {
+                    query.push("value LIKE ");
+                    query.push_bind(format!("%{}", escape_like(suffix)));
+                    query.push(" ESCAPE '\\'");
+                }
+                Condition::ContainsSubstring(substring) => {
+                    query.push("value LIKE ");
+                    query.push_bind(format!("%{}%", escape_like(substring)));
+                    query.push(" ESCAPE '\\'");
+                }
+            }
+            query.push(")");
+        }
+    }
+}
+
+/// Pushes the full-text search rank of the value against `q`; only called when `q` is set.
+fn push_rank(query: &mut QueryBuilder<'_, Postgres>, filter_values: &StringFilters) {
+    let language = filter_values.language.unwrap_or_default();
+    query.push(format!("ts_rank_cd({}, ", language.vector()));
+    push_tsquery(
+        query,
+        language,
+        filter_values.q.as_deref().unwrap_or_default(),
+    );
+    query.push(")");
+}
+
+fn push_tsquery(query: &mut QueryBuilder<'_, Postgres>, language: SearchLanguage, q: &str) {
+    query.push(format!("websearch_to_tsquery({}, ", language.config()));
+    query.push_bind(q.to_string());
+    query.push(")");
+}
@@ -0,0 +1,164 @@
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::{
+    cache::service::CacheService,
+    db::repositories::StringRepository,
+    models::{
+        backfill::{BackfillProgress, BackfillRequest, BackfillStatus},
+        properties::AnalysedString,
+    },
+    utils::analyser::{analyse_string, registry::registry},
+};
+
+pub const DEFAULT_BATCH_SIZE: i64 = 500;
+pub const MAX_BATCH_SIZE: i64 = 5000;
+
+pub enum BackfillStart {
+    Started(BackfillProgress),
+    AlreadyRunning(BackfillProgress),
+}
+
+/// Creates the checkpoint a run works from. An interrupted or failed run with the same
+/// scope is resumed after its `last_id` unless `restart` is set.
+///
+/// The checkpoint is only written if the stored one has not changed since it was read, so
+/// of two concurrent starts exactly one wins and the other sees it running.
+pub async fn start_backfill(
+    repository: &StringRepository,
+    cache: &CacheService,
+    request: &BackfillRequest,
+) -> Result<BackfillStart> {
+    loop {
+        let stored = cache.get_backfill_progress_json().await?;
+        let previous: Option<BackfillProgress> = stored
+            .as_deref()
+            .and_then(|json_data| serde_json::from_str(json_data).ok());
+
+        if let Some(ref previous) = previous
+            && previous.status == BackfillStatus::Running
+            && !previous.is_stale()
+        {
+            return Ok(BackfillStart::AlreadyRunning(previous.clone()));
+        }
+
+        let progress = match previous {
+            Some(mut previous)
+                if !request.restart
+                    && previous.status != BackfillStatus::Completed
+                    && previous.all == request.all =>
+            {
+                tracing::info!(
+                    "Resuming backfill after {:?} ({}/{} rows processed)",
+                    previous.last_id,
+                    previous.processed,
+                    previous.total
+                );
+
+                previous.status = BackfillStatus::Running;
+                previous.error = None;
+                previous.updated_at = Utc::now();
+                previous
+            }
+            _ => {
+                let versions = registry().versions();
+                let scope = (!request.all).then_some(&versions);
+                let total = repository.count_for_backfill(scope).await?;
+
+                BackfillProgress::new(request.all, total)
+            }
+        };
+
+        if cache
+            .replace_backfill_progress(stored.as_deref(), &progress)
+            .await?
+        {
+            return Ok(BackfillStart::Started(progress));
+        }
+
+        // Another start wrote its checkpoint in between; look at it again.
+    }
+}
+
+/// Pages through `analysed_strings` by `id`, re-analyses each value and writes the batch
+/// back, checkpointing progress in Redis after every batch.
+pub async fn run_backfill(
+    repository: &StringRepository,
+    cache: &CacheService,
+    mut progress: BackfillProgress,
+    batch_size: Option<i64>,
+) -> Result<BackfillProgress> {
+    let batch_size = batch_size
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+        .clamp(1, MAX_BATCH_SIZE);
+
+    match run_batches(repository, cache, &mut progress, batch_size).await {
+        Ok(()) => {
+            progress.status = BackfillStatus::Completed;
+            progress.updated_at = Utc::now();
+            cache.set_backfill_progress(&progress).await?;
+
+            let _ = cache.invalidate().await;
+
+            tracing::info!("Backfill completed: {} rows processed", progress.processed);
+
+            Ok(progress)
+        }
+        Err(e) => {
+            tracing::error!("Backfill failed after {:?}: {:?}", progress.last_id, e);
+
+            progress.status = BackfillStatus::Failed;
+            progress.error = Some(e.to_string());
+            progress.updated_at = Utc::now();
+            let _ = cache.set_backfill_progress(&progress).await;
+
+            Err(e)
+        }
+    }
+}
+
+async fn run_batches(
+    repository: &StringRepository,
+    cache: &CacheService,
+    progress: &mut BackfillProgress,
+    batch_size: i64,
+) -> Result<()> {
+    let versions = registry().versions();
+    let scope = (!progress.all).then_some(&versions);
+
+    loop {
+        let batch = repository
+            .page_for_backfill(progress.last_id.as_deref(), batch_size, scope)
+            .await?;
+
+        let Some(last) = batch.last() else {
+            return Ok(());
+        };
+        let last_id = last.id.clone();
+
+        let reanalysed = batch
+            .into_iter()
+            .map(|analysed_string| AnalysedString {
+                properties: analyse_string(&analysed_string.value),
+                ..analysed_string
+            })
+            .collect::<Vec<AnalysedString>>();
+
+        repository.update_properties(&reanalysed).await?;
+
+        for analysed_string in &reanalysed {
+            let _ = cache.refresh(analysed_string).await;
+        }
+
+        progress.processed += reanalysed.len() as i64;
+        progress.last_id = Some(last_id);
+        progress.updated_at = Utc::now();
+        cache.set_backfill_progress(progress).await?;
+
+        tracing::info!(
+            "Backfill progress: {}/{} rows processed",
+            progress.processed,
+            progress.total
+        );
+    }
+}
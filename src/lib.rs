@@ -1,6 +1,7 @@
 pub mod api;
 pub mod cache;
 pub mod db;
+pub mod jobs;
 pub mod middleware;
 pub mod models;
 pub mod routes;
@@ -5,8 +5,11 @@ use string_analyser::{
     api::build_app,
     cache::{connection::create_redis_client, service::CacheService},
     db::{pool::create_pool, repositories::StringRepository},
-    models::state::AppState,
-    utils::config::load_config,
+    jobs::backfill::{BackfillStart, run_backfill, start_backfill},
+    models::{backfill::BackfillRequest, state::AppState},
+    utils::config::{
+        load_admin_token, load_config, load_cursor_secret, load_nlp_options, load_nlp_vocabulary,
+    },
 };
 use tokio::net::TcpListener;
 
@@ -18,7 +21,7 @@ async fn main() -> Result<()> {
 
     let config_data = load_config()?;
 
-    let pool = create_pool(&config_data.0, *&config_data.2, *&config_data.3)
+    let pool = create_pool(&config_data.0, config_data.2, config_data.3)
         .await
         .map_err(|e| anyhow!("Database connection error {}", e))?;
 
@@ -30,7 +33,24 @@ async fn main() -> Result<()> {
 
     let cache = CacheService::new(redis);
 
-    let state = AppState { repository, cache };
+    let nlp_options = load_nlp_options()?;
+
+    let vocabulary = load_nlp_vocabulary()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    if args.first().map(String::as_str) == Some("backfill") {
+        return backfill(&repository, &cache, &args[1..]).await;
+    }
+
+    let state = AppState {
+        repository,
+        cache,
+        admin_token: load_admin_token(),
+        cursor_secret: load_cursor_secret()?,
+        nlp_options,
+        vocabulary,
+    };
 
     let app = build_app(state).await;
 
@@ -49,3 +69,53 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// `string_analyser backfill [--all] [--restart] [--batch-size N]`
+async fn backfill(
+    repository: &StringRepository,
+    cache: &CacheService,
+    args: &[String],
+) -> Result<()> {
+    let mut request = BackfillRequest::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => request.all = true,
+            "--restart" => request.restart = true,
+            "--batch-size" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for --batch-size"))?;
+
+                request.batch_size = Some(
+                    value
+                        .parse::<i64>()
+                        .map_err(|e| anyhow!("Invalid --batch-size '{}': {}", value, e))?,
+                );
+            }
+            _ => return Err(anyhow!("Unknown backfill argument '{}'", arg)),
+        }
+    }
+
+    let progress = match start_backfill(repository, cache, &request).await? {
+        BackfillStart::Started(progress) => progress,
+        BackfillStart::AlreadyRunning(progress) => {
+            return Err(anyhow!(
+                "A backfill is already running ({}/{} rows processed)",
+                progress.processed,
+                progress.total
+            ));
+        }
+    };
+
+    let progress = run_backfill(repository, cache, progress, request.batch_size).await?;
+
+    tracing::info!(
+        "Backfill finished: {}/{} rows processed",
+        progress.processed,
+        progress.total
+    );
+
+    Ok(())
+}
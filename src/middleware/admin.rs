@@ -0,0 +1,50 @@
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{StatusCode, header::AUTHORIZATION},
+    middleware::Next,
+    response::IntoResponse,
+};
+use subtle::ConstantTimeEq;
+
+use crate::models::{responses::ApiErrorResponse, state::AppState};
+
+pub async fn require_admin_token(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let Some(ref admin_token) = state.admin_token else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiErrorResponse::not_found(
+                "Admin endpoints are disabled".to_string(),
+                None,
+            )),
+        )
+            .into_response();
+    };
+
+    let bearer_token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "));
+
+    // Constant time, so response timing does not reveal how much of a guess was right.
+    let authorised = bearer_token
+        .is_some_and(|token| bool::from(token.as_bytes().ct_eq(admin_token.as_bytes())));
+
+    if !authorised {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiErrorResponse::unauthorized(
+                "Missing or invalid admin token".to_string(),
+                None,
+            )),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnalyserInfo {
+    pub name: String,
+    pub version: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnalysersResponse {
+    pub analysers: Vec<AnalyserInfo>,
+    /// Stored strings computed by a missing or older version of at least one analyser.
+    pub outdated_count: i64,
+}
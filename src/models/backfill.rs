@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct BackfillRequest {
+    /// Rows re-analysed per batch (default 500, max 5000).
+    pub batch_size: Option<i64>,
+    /// Re-analyse every row instead of only rows with outdated analyser versions.
+    #[serde(default)]
+    pub all: bool,
+    /// Ignore the checkpoint of an interrupted run and start from the beginning.
+    #[serde(default)]
+    pub restart: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BackfillStatus {
+    #[default]
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Checkpoint of a backfill run, stored in Redis under `backfill:progress`.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct BackfillProgress {
+    pub status: BackfillStatus,
+    pub all: bool,
+    /// Rows to visit, counted when the run started.
+    pub total: i64,
+    pub processed: i64,
+    /// Last `id` written; an interrupted run resumes after it.
+    pub last_id: Option<String>,
+    pub error: Option<String>,
+    #[schema(value_type = String)]
+    pub started_at: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BackfillProgress {
+    pub fn new(all: bool, total: i64) -> Self {
+        let now = Utc::now();
+
+        Self {
+            status: BackfillStatus::Running,
+            all,
+            total,
+            processed: 0,
+            last_id: None,
+            error: None,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// A run still marked as running that has not checkpointed recently was interrupted.
+    pub fn is_stale(&self) -> bool {
+        Utc::now() - self.updated_at > chrono::Duration::minutes(5)
+    }
+}
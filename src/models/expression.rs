@@ -0,0 +1,195 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::models::filters::{PalindromeMode, Timestamp};
+
+/// Parsed `filter` expression, e.g. `(is_palindrome:true OR word_count:1) AND NOT contains:z`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpression {
+    And(Box<FilterExpression>, Box<FilterExpression>),
+    Or(Box<FilterExpression>, Box<FilterExpression>),
+    Not(Box<FilterExpression>),
+    Condition(Condition),
+}
+
+/// A single `key:value` term. Column names come from a fixed list, never from the input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Palindrome {
+        mode: PalindromeMode,
+        value: bool,
+    },
+    Numeric {
+        column: &'static str,
+        comparison: Comparison,
+        value: i32,
+    },
+    /// A fractional column such as `vowel_ratio`.
+    Ratio {
+        column: &'static str,
+        comparison: Comparison,
+        value: f64,
+    },
+    /// `created_at` compared with a timestamp; always has a comparison.
+    CreatedAt {
+        comparison: Comparison,
+        value: Timestamp,
+    },
+    /// Every character must occur.
+    Contains(Vec<String>),
+    StartsWith(String),
+    EndsWith(String),
+    ContainsSubstring(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            Comparison::Equal => "=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+        }
+    }
+}
+
+/// Renders the expression back into `filter` syntax; the result parses to the same tree.
+impl fmt::Display for FilterExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterExpression::And(left, right) => {
+                write_operand(f, left, matches!(**left, FilterExpression::Or(..)))?;
+                write!(f, " AND ")?;
+                write_operand(
+                    f,
+                    right,
+                    !matches!(
+                        **right,
+                        FilterExpression::Condition(_) | FilterExpression::Not(_)
+                    ),
+                )
+            }
+            FilterExpression::Or(left, right) => {
+                write!(f, "{} OR ", left)?;
+                write_operand(f, right, matches!(**right, FilterExpression::Or(..)))
+            }
+            FilterExpression::Not(inner) => {
+                write!(f, "NOT ")?;
+                write_operand(
+                    f,
+                    inner,
+                    matches!(
+                        **inner,
+                        FilterExpression::And(..) | FilterExpression::Or(..)
+                    ),
+                )
+            }
+            FilterExpression::Condition(condition) => write!(f, "{}", condition),
+        }
+    }
+}
+
+fn write_operand(
+    f: &mut fmt::Formatter<'_>,
+    operand: &FilterExpression,
+    parenthesise: bool,
+) -> fmt::Result {
+    if parenthesise {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Palindrome { mode, value } => write!(f, "{}:{}", mode.column(), value),
+            Condition::Numeric {
+                column,
+                comparison,
+                value,
+            } => {
+                let key = match *column {
+                    "unique_char_count" => "unique_characters",
+                    column => column,
+                };
+                let comparison = match comparison {
+                    Comparison::Equal => "",
+                    comparison => comparison.sql(),
+                };
+                write!(f, "{}:{}{}", key, comparison, value)
+            }
+            Condition::Ratio {
+                column,
+                comparison,
+                value,
+            } => {
+                let comparison = match comparison {
+                    Comparison::Equal => "",
+                    comparison => comparison.sql(),
+                };
+                write!(f, "{}:{}{}", column, comparison, value)
+            }
+            Condition::CreatedAt { comparison, value } => {
+                write!(f, "created_at:{}{}", comparison.sql(), value)
+            }
+            Condition::Contains(characters) => {
+                write!(f, "contains:{}", quote_value(&characters.concat()))
+            }
+            Condition::StartsWith(value) => write!(f, "starts_with:{}", quote_value(value)),
+            Condition::EndsWith(value) => write!(f, "ends_with:{}", quote_value(value)),
+            Condition::ContainsSubstring(value) => {
+                write!(f, "contains_substring:{}", quote_value(value))
+            }
+        }
+    }
+}
+
+/// Quotes a value unless it can be written as a bare word.
+fn quote_value(value: &str) -> String {
+    let bare = !value.is_empty()
+        && !value.starts_with(['"', '<', '>'])
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '(' | ')'));
+
+    if bare {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Why a `filter` expression could not be parsed, and the character offset it refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExpressionError {
+    pub reason: String,
+    pub position: usize,
+}
+
+impl ExpressionError {
+    pub fn new(reason: impl Into<String>, position: usize) -> Self {
+        Self {
+            reason: reason.into(),
+            position,
+        }
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.reason, self.position)
+    }
+}
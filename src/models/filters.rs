@@ -1,10 +1,880 @@
-use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
 
-#[derive(Debug, Deserialize, Serialize)]
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+use crate::{
+    models::expression::{ExpressionError, FilterExpression},
+    utils::expression::parse_filter_expression,
+};
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct StringFilters {
     pub is_palindrome: Option<bool>,
+    pub palindrome_mode: Option<PalindromeMode>,
+    pub length: Option<i32>,
     pub min_length: Option<i32>,
     pub max_length: Option<i32>,
+    pub length_unit: Option<LengthUnit>,
     pub word_count: Option<i32>,
+    pub min_word_count: Option<i32>,
+    pub max_word_count: Option<i32>,
+    pub unique_characters: Option<i32>,
+    pub min_unique_characters: Option<i32>,
+    pub max_unique_characters: Option<i32>,
+    pub digit_count: Option<i32>,
+    pub min_digit_count: Option<i32>,
+    pub max_digit_count: Option<i32>,
+    pub uppercase_count: Option<i32>,
+    pub min_uppercase_count: Option<i32>,
+    pub max_uppercase_count: Option<i32>,
+    pub lowercase_count: Option<i32>,
+    pub min_lowercase_count: Option<i32>,
+    pub max_lowercase_count: Option<i32>,
+    pub punctuation_count: Option<i32>,
+    pub min_punctuation_count: Option<i32>,
+    pub max_punctuation_count: Option<i32>,
+    pub whitespace_count: Option<i32>,
+    pub min_whitespace_count: Option<i32>,
+    pub max_whitespace_count: Option<i32>,
+    pub symbol_count: Option<i32>,
+    pub min_symbol_count: Option<i32>,
+    pub max_symbol_count: Option<i32>,
+    pub emoji_count: Option<i32>,
+    pub min_emoji_count: Option<i32>,
+    pub max_emoji_count: Option<i32>,
+    pub vowel_count: Option<i32>,
+    pub min_vowel_count: Option<i32>,
+    pub max_vowel_count: Option<i32>,
+    pub consonant_count: Option<i32>,
+    pub min_consonant_count: Option<i32>,
+    pub max_consonant_count: Option<i32>,
+    /// Share of vowels among the letters, from 0 to 1.
+    pub min_vowel_ratio: Option<f64>,
+    pub max_vowel_ratio: Option<f64>,
+    pub longest_consonant_run: Option<i32>,
+    pub min_longest_consonant_run: Option<i32>,
+    pub max_longest_consonant_run: Option<i32>,
     pub contains_character: Option<String>,
+    /// Every listed character must occur, e.g. `abc`.
+    pub contains_all: Option<String>,
+    /// At least one listed character must occur.
+    pub contains_any: Option<String>,
+    /// None of the listed characters may occur.
+    pub excludes: Option<String>,
+    /// Minimum occurrences per character, e.g. `e:3,l:2`.
+    pub min_char_count: Option<CharCounts>,
+    /// Maximum occurrences per character, e.g. `z:0`.
+    pub max_char_count: Option<CharCounts>,
+    pub starts_with: Option<String>,
+    pub ends_with: Option<String>,
+    pub contains_substring: Option<String>,
+    /// Postgres (POSIX-style) regular expression matched against the value.
+    pub regex: Option<String>,
+    /// Applies to `starts_with`, `ends_with`, `contains_substring` and `regex` (default false).
+    pub case_insensitive: Option<bool>,
+    /// Created at or after this instant.
+    pub created_after: Option<Timestamp>,
+    /// Created before this instant.
+    pub created_before: Option<Timestamp>,
+    /// Boolean expression ANDed with the other filters, e.g.
+    /// `(is_palindrome:true OR word_count:1) AND NOT contains:z`.
+    pub filter: Option<String>,
+    /// `filter` once parsed by `parse_expression`.
+    #[serde(skip)]
+    pub expression: Option<FilterExpression>,
+    /// Full-text search query in web search syntax: `"quoted phrase"`, `or`, `-excluded`.
+    pub q: Option<String>,
+    /// Text search configuration `q` is stemmed with (default english).
+    pub language: Option<SearchLanguage>,
+    /// Defaults to `relevance` when `q` is given and `created_at` otherwise.
+    pub sort: Option<SortField>,
+    /// Defaults to `desc` for `created_at` and `relevance`, `asc` for every other sort field.
+    pub order: Option<SortOrder>,
+    /// Page size, capped at `MAX_PAGE_LIMIT`.
+    pub limit: Option<i64>,
+    /// At most this many matches in total, across every page ("the 5 longest").
+    pub max_results: Option<i64>,
+    /// `next_cursor` from the previous page.
+    #[serde(skip_serializing)]
+    pub cursor: Option<String>,
+    /// Also count every row matching the filters.
+    #[serde(skip_serializing)]
+    pub include_total: Option<bool>,
+}
+
+/// Exact and min/max bounds on one numeric column, all inclusive.
+pub struct NumericRange {
+    pub name: &'static str,
+    pub column: &'static str,
+    pub exact: Option<i32>,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl StringFilters {
+    /// Parses `filter` into `expression`; must be called before the filters are queried.
+    pub fn parse_expression(&mut self) -> Result<(), ExpressionError> {
+        self.expression = self
+            .filter
+            .as_deref()
+            .map(parse_filter_expression)
+            .transpose()?;
+
+        Ok(())
+    }
+
+    /// Every numeric property that can be filtered. A new numeric column only needs its
+    /// fields above and an entry here to be filtered and validated.
+    pub fn numeric_ranges(&self) -> [NumericRange; 13] {
+        [
+            NumericRange {
+                name: "length",
+                column: self.length_unit.unwrap_or_default().column(),
+                exact: self.length,
+                min: self.min_length,
+                max: self.max_length,
+            },
+            NumericRange {
+                name: "word_count",
+                column: "word_count",
+                exact: self.word_count,
+                min: self.min_word_count,
+                max: self.max_word_count,
+            },
+            NumericRange {
+                name: "unique_characters",
+                column: "unique_char_count",
+                exact: self.unique_characters,
+                min: self.min_unique_characters,
+                max: self.max_unique_characters,
+            },
+            NumericRange {
+                name: "digit_count",
+                column: "digit_count",
+                exact: self.digit_count,
+                min: self.min_digit_count,
+                max: self.max_digit_count,
+            },
+            NumericRange {
+                name: "uppercase_count",
+                column: "uppercase_count",
+                exact: self.uppercase_count,
+                min: self.min_uppercase_count,
+                max: self.max_uppercase_count,
+            },
+            NumericRange {
+                name: "lowercase_count",
+                column: "lowercase_count",
+                exact: self.lowercase_count,
+                min: self.min_lowercase_count,
+                max: self.max_lowercase_count,
+            },
+            NumericRange {
+                name: "punctuation_count",
+                column: "punctuation_count",
+                exact: self.punctuation_count,
+                min: self.min_punctuation_count,
+                max: self.max_punctuation_count,
+            },
+            NumericRange {
+                name: "whitespace_count",
+                column: "whitespace_count",
+                exact: self.whitespace_count,
+                min: self.min_whitespace_count,
+                max: self.max_whitespace_count,
+            },
+            NumericRange {
+                name: "symbol_count",
+                column: "symbol_count",
+                exact: self.symbol_count,
+                min: self.min_symbol_count,
+                max: self.max_symbol_count,
+            },
+            NumericRange {
+                name: "emoji_count",
+                column: "emoji_count",
+                exact: self.emoji_count,
+                min: self.min_emoji_count,
+                max: self.max_emoji_count,
+            },
+            NumericRange {
+                name: "vowel_count",
+                column: "vowel_count",
+                exact: self.vowel_count,
+                min: self.min_vowel_count,
+                max: self.max_vowel_count,
+            },
+            NumericRange {
+                name: "consonant_count",
+                column: "consonant_count",
+                exact: self.consonant_count,
+                min: self.min_consonant_count,
+                max: self.max_consonant_count,
+            },
+            NumericRange {
+                name: "longest_consonant_run",
+                column: "longest_consonant_run",
+                exact: self.longest_consonant_run,
+                min: self.min_longest_consonant_run,
+                max: self.max_longest_consonant_run,
+            },
+        ]
+    }
+
+    /// The exact, min and max bounds on a character class count.
+    pub fn class_bounds_mut(
+        &mut self,
+        class: CharacterClass,
+    ) -> (&mut Option<i32>, &mut Option<i32>, &mut Option<i32>) {
+        match class {
+            CharacterClass::Digit => (
+                &mut self.digit_count,
+                &mut self.min_digit_count,
+                &mut self.max_digit_count,
+            ),
+            CharacterClass::Uppercase => (
+                &mut self.uppercase_count,
+                &mut self.min_uppercase_count,
+                &mut self.max_uppercase_count,
+            ),
+            CharacterClass::Lowercase => (
+                &mut self.lowercase_count,
+                &mut self.min_lowercase_count,
+                &mut self.max_lowercase_count,
+            ),
+            CharacterClass::Punctuation => (
+                &mut self.punctuation_count,
+                &mut self.min_punctuation_count,
+                &mut self.max_punctuation_count,
+            ),
+            CharacterClass::Whitespace => (
+                &mut self.whitespace_count,
+                &mut self.min_whitespace_count,
+                &mut self.max_whitespace_count,
+            ),
+            CharacterClass::Symbol => (
+                &mut self.symbol_count,
+                &mut self.min_symbol_count,
+                &mut self.max_symbol_count,
+            ),
+            CharacterClass::Emoji => (
+                &mut self.emoji_count,
+                &mut self.min_emoji_count,
+                &mut self.max_emoji_count,
+            ),
+            CharacterClass::Vowel => (
+                &mut self.vowel_count,
+                &mut self.min_vowel_count,
+                &mut self.max_vowel_count,
+            ),
+            CharacterClass::Consonant => (
+                &mut self.consonant_count,
+                &mut self.min_consonant_count,
+                &mut self.max_consonant_count,
+            ),
+        }
+    }
+
+    /// Rejects bounds that no row could satisfy.
+    pub fn validate(&self) -> Result<(), String> {
+        for range in self.numeric_ranges() {
+            if let (Some(min), Some(max)) = (range.min, range.max)
+                && min > max
+            {
+                return Err(format!(
+                    "Conflicting filters: min_{} > max_{}",
+                    range.name, range.name
+                ));
+            }
+
+            if let Some(exact) = range.exact
+                && (range.min.is_some_and(|min| exact < min)
+                    || range.max.is_some_and(|max| exact > max))
+            {
+                return Err(format!(
+                    "Conflicting filters: {} outside min_{}..max_{}",
+                    range.name, range.name, range.name
+                ));
+            }
+        }
+
+        for ratio in [self.min_vowel_ratio, self.max_vowel_ratio]
+            .into_iter()
+            .flatten()
+        {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(
+                    "min_vowel_ratio and max_vowel_ratio must be between 0 and 1".to_string(),
+                );
+            }
+        }
+
+        if let (Some(min), Some(max)) = (self.min_vowel_ratio, self.max_vowel_ratio)
+            && min > max
+        {
+            return Err("Conflicting filters: min_vowel_ratio > max_vowel_ratio".to_string());
+        }
+
+        if let Some(ref regex) = self.regex {
+            validate_regex(regex)?;
+        }
+
+        if let Some(ref q) = self.q
+            && (q.trim().is_empty() || q.chars().count() > MAX_SEARCH_QUERY_LENGTH)
+        {
+            return Err(format!(
+                "q must be between 1 and {} characters",
+                MAX_SEARCH_QUERY_LENGTH
+            ));
+        }
+
+        if let (Some(after), Some(before)) = (self.created_after, self.created_before)
+            && after >= before
+        {
+            return Err(
+                "Conflicting filters: created_after must be before created_before".to_string(),
+            );
+        }
+
+        if self.max_results.is_some_and(|max_results| max_results < 1) {
+            return Err("max_results must be at least 1".to_string());
+        }
+
+        if self.sort == Some(SortField::Relevance) && self.q.is_none() {
+            return Err("sort=relevance requires q".to_string());
+        }
+
+        if let Some(ref contains_character) = self.contains_character
+            && contains_character.chars().count() != 1
+        {
+            return Err("contains_character must be a single character".to_string());
+        }
+
+        let excluded = character_set(self.excludes.as_deref());
+        let required = character_set(self.contains_all.as_deref())
+            .into_iter()
+            .chain(character_set(self.contains_character.as_deref()))
+            .chain(
+                self.min_char_count
+                    .iter()
+                    .flat_map(|counts| counts.0.iter())
+                    .filter(|(_, count)| **count > 0)
+                    .map(|(character, _)| character.clone()),
+            );
+
+        for character in required {
+            if excluded.contains(&character) {
+                return Err(format!(
+                    "Conflicting filters: '{}' is both required and excluded",
+                    character
+                ));
+            }
+        }
+
+        if let (Some(min_counts), Some(max_counts)) = (&self.min_char_count, &self.max_char_count) {
+            for (character, min) in &min_counts.0 {
+                if max_counts.0.get(character).is_some_and(|max| min > max) {
+                    return Err(format!(
+                        "Conflicting filters: min_char_count > max_char_count for '{}'",
+                        character
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The filters that are set, as echoed in `filters_applied` and `parsed_filters`.
+    pub fn applied(&self) -> serde_json::Value {
+        let mut applied = serde_json::to_value(self).unwrap_or(serde_json::json!({}));
+
+        if let Some(obj) = applied.as_object_mut() {
+            obj.retain(|_, v| !v.is_null());
+        }
+
+        applied
+    }
+
+    pub fn sort_field(&self) -> SortField {
+        self.sort.unwrap_or(if self.q.is_some() {
+            SortField::Relevance
+        } else {
+            SortField::CreatedAt
+        })
+    }
+
+    /// Column the listing is ordered by; `sort=length` follows `length_unit` and
+    /// `sort=relevance` orders by the computed `rank`.
+    pub fn sort_column(&self) -> &'static str {
+        match self.sort_field() {
+            SortField::CreatedAt => "created_at",
+            SortField::Length => self.length_unit.unwrap_or_default().column(),
+            SortField::WordCount => "word_count",
+            SortField::UniqueCharacters => "unique_char_count",
+            SortField::Value => "value",
+            SortField::Relevance => "rank",
+        }
+    }
+
+    pub fn sort_order(&self) -> SortOrder {
+        self.order.unwrap_or(match self.sort_field() {
+            SortField::CreatedAt | SortField::Relevance => SortOrder::Desc,
+            _ => SortOrder::Asc,
+        })
+    }
+}
+
+/// Which length metric `min_length`/`max_length` are compared against.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LengthUnit {
+    Bytes,
+    Codepoints,
+    #[default]
+    Graphemes,
+}
+
+impl LengthUnit {
+    pub fn column(&self) -> &'static str {
+        match self {
+            LengthUnit::Bytes => "byte_length",
+            LengthUnit::Codepoints => "codepoint_length",
+            LengthUnit::Graphemes => "length",
+        }
+    }
+}
+
+/// Which palindrome check `is_palindrome` is compared against.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PalindromeMode {
+    #[default]
+    Strict,
+    #[serde(alias = "alphanumeric")]
+    Loose,
+    #[serde(alias = "normalized")]
+    Normalised,
+}
+
+impl PalindromeMode {
+    pub fn column(&self) -> &'static str {
+        match self {
+            PalindromeMode::Strict => "is_palindrome",
+            PalindromeMode::Loose => "is_loose_palindrome",
+            PalindromeMode::Normalised => "is_normalised_palindrome",
+        }
+    }
+}
+
+/// A class of characters counted per string, each filtered by its own `<class>_count`
+/// column. Vowels and consonants are counted by the vowel analyser, the rest by the
+/// character class analyser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterClass {
+    Digit,
+    Uppercase,
+    Lowercase,
+    Punctuation,
+    Whitespace,
+    Symbol,
+    Emoji,
+    Vowel,
+    Consonant,
+}
+
+impl CharacterClass {
+    pub const ALL: [CharacterClass; 9] = [
+        CharacterClass::Digit,
+        CharacterClass::Uppercase,
+        CharacterClass::Lowercase,
+        CharacterClass::Punctuation,
+        CharacterClass::Whitespace,
+        CharacterClass::Symbol,
+        CharacterClass::Emoji,
+        CharacterClass::Vowel,
+        CharacterClass::Consonant,
+    ];
+
+    pub fn column(&self) -> &'static str {
+        match self {
+            CharacterClass::Digit => "digit_count",
+            CharacterClass::Uppercase => "uppercase_count",
+            CharacterClass::Lowercase => "lowercase_count",
+            CharacterClass::Punctuation => "punctuation_count",
+            CharacterClass::Whitespace => "whitespace_count",
+            CharacterClass::Symbol => "symbol_count",
+            CharacterClass::Emoji => "emoji_count",
+            CharacterClass::Vowel => "vowel_count",
+            CharacterClass::Consonant => "consonant_count",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    #[default]
+    CreatedAt,
+    Length,
+    WordCount,
+    #[serde(alias = "unique_chars")]
+    UniqueCharacters,
+    #[serde(alias = "alphabetical")]
+    Value,
+    /// Full-text search rank of `q`.
+    Relevance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    /// Row comparison operator that continues past a keyset cursor in this order.
+    pub fn keyset_operator(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => ">",
+            SortOrder::Desc => "<",
+        }
+    }
+
+    pub fn sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// Text search configuration used to stem `q` and the stored values. Every variant has a
+/// matching GIN index, so the configuration is always written into the SQL as a literal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchLanguage {
+    #[default]
+    English,
+    French,
+    Spanish,
+    /// No stemming or stop words.
+    Simple,
+}
+
+impl SearchLanguage {
+    pub fn config(&self) -> &'static str {
+        match self {
+            SearchLanguage::English => "'english'::regconfig",
+            SearchLanguage::French => "'french'::regconfig",
+            SearchLanguage::Spanish => "'spanish'::regconfig",
+            SearchLanguage::Simple => "'simple'::regconfig",
+        }
+    }
+
+    /// Indexed `tsvector` of the value under this configuration.
+    pub fn vector(&self) -> &'static str {
+        match self {
+            SearchLanguage::English => "search_vector",
+            SearchLanguage::French => "to_tsvector('french'::regconfig, value)",
+            SearchLanguage::Spanish => "to_tsvector('spanish'::regconfig, value)",
+            SearchLanguage::Simple => "to_tsvector('simple'::regconfig, value)",
+        }
+    }
+}
+
+pub const MAX_SEARCH_QUERY_LENGTH: usize = 256;
+
+pub const MAX_REGEX_LENGTH: usize = 256;
+
+/// Rejects regular expressions likely to be slow to evaluate: overly long patterns,
+/// backreferences and quantified groups that already contain a quantifier (`(a+)+`).
+/// Bracket expressions are skipped, so `[)]` and `([*])+` are plain character classes.
+fn validate_regex(pattern: &str) -> Result<(), String> {
+    if pattern.is_empty() || pattern.chars().count() > MAX_REGEX_LENGTH {
+        return Err(format!(
+            "regex must be between 1 and {} characters",
+            MAX_REGEX_LENGTH
+        ));
+    }
+
+    let chars = pattern.chars().collect::<Vec<char>>();
+    // Whether each currently open group contains a quantifier.
+    let mut groups = vec![false];
+    let mut index = 0;
+
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => {
+                if chars.get(index + 1).is_some_and(|c| c.is_ascii_digit()) {
+                    return Err("regex backreferences are not allowed".to_string());
+                }
+                index += 1;
+            }
+            '[' => index = bracket_end(&chars, index),
+            '(' => groups.push(false),
+            ')' => {
+                let quantified_inside = groups.pop().unwrap_or(false);
+                let quantified_after = chars
+                    .get(index + 1)
+                    .is_some_and(|c| matches!(c, '*' | '+' | '?' | '{'));
+
+                if quantified_inside && quantified_after {
+                    return Err("regex nested quantifiers are not allowed".to_string());
+                }
+                if groups.is_empty() {
+                    groups.push(false);
+                }
+                if quantified_inside || quantified_after {
+                    *groups.last_mut().unwrap() = true;
+                }
+            }
+            '*' | '+' | '?' | '{' => *groups.last_mut().unwrap() = true,
+            _ => {}
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Index of the `]` closing the bracket expression opened at `start`, or the end of the
+/// pattern when it is unclosed. A `]` first in the class (`[]a]`, `[^]a]`) or escaped is
+/// literal, and `[:class:]`, `[.x.]` and `[=x=]` may appear inside.
+fn bracket_end(chars: &[char], start: usize) -> usize {
+    let mut index = start + 1;
+    if chars.get(index) == Some(&'^') {
+        index += 1;
+    }
+    if chars.get(index) == Some(&']') {
+        index += 1;
+    }
+
+    while index < chars.len() {
+        match chars[index] {
+            ']' => return index,
+            '\\' => index += 1,
+            '[' if chars
+                .get(index + 1)
+                .is_some_and(|c| matches!(c, ':' | '.' | '=')) =>
+            {
+                let delimiter = chars[index + 1];
+                index += 2;
+                while index < chars.len()
+                    && !(chars[index] == delimiter && chars.get(index + 1) == Some(&']'))
+                {
+                    index += 1;
+                }
+                index += 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    chars.len()
+}
+
+/// Escapes `%`, `_` and `\` so `input` matches literally inside a `LIKE` pattern.
+pub fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for ch in input.chars() {
+        if matches!(ch, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+
+    escaped
+}
+
+/// Lowercased, de-duplicated characters of a `contains_all`/`contains_any`/`excludes` value,
+/// matching the keys of `char_frequency_map`.
+pub fn character_set(characters: Option<&str>) -> Vec<String> {
+    let mut set = Vec::new();
+
+    for character in characters.unwrap_or_default().to_lowercase().chars() {
+        let character = character.to_string();
+        if !set.contains(&character) {
+            set.push(character);
+        }
+    }
+
+    set
+}
+
+/// A point in time, written as an RFC 3339 timestamp or a `YYYY-MM-DD` date meaning its
+/// midnight UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp(pub DateTime<Utc>);
+
+impl Timestamp {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(Self::midnight(date));
+        }
+
+        DateTime::parse_from_rfc3339(value)
+            .map(|timestamp| Self(timestamp.with_timezone(&Utc)))
+            .map_err(|_| {
+                format!(
+                    "Invalid timestamp '{}', expected RFC 3339 or YYYY-MM-DD",
+                    value
+                )
+            })
+    }
+
+    /// Start of `date` in UTC.
+    pub fn midnight(date: NaiveDate) -> Self {
+        Self(date.and_time(NaiveTime::MIN).and_utc())
+    }
+
+    /// The date alone when the timestamp is a UTC midnight.
+    pub fn date(&self) -> Option<NaiveDate> {
+        (self.0.time() == NaiveTime::MIN).then(|| self.0.date_naive())
+    }
+}
+
+/// Written the way `created_at` is returned, e.g. `2026-01-01T10:00:00.000Z`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Timestamp::parse(&value).map_err(D::Error::custom)
+    }
+}
+
+/// Per-character occurrence thresholds, written as `e:3,l:2` in query strings.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CharCounts(pub BTreeMap<String, i32>);
+
+impl CharCounts {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut counts = BTreeMap::new();
+
+        for pair in value.split(',').filter(|pair| !pair.is_empty()) {
+            let (character, count) = pair
+                .rsplit_once(':')
+                .ok_or_else(|| format!("Invalid character count '{}'", pair))?;
+
+            let mut characters = character.chars();
+            let (Some(character), None) = (characters.next(), characters.next()) else {
+                return Err(format!("Invalid character count '{}'", pair));
+            };
+
+            let count = count
+                .parse::<i32>()
+                .ok()
+                .filter(|count| *count >= 0)
+                .ok_or_else(|| format!("Invalid character count '{}'", pair))?;
+
+            counts.insert(character.to_lowercase().to_string(), count);
+        }
+
+        Ok(Self(counts))
+    }
+}
+
+impl fmt::Display for CharCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(character, count)| format!("{}:{}", character, count))
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+impl Serialize for CharCounts {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for CharCounts {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        CharCounts::parse(&value).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_REGEX_LENGTH, validate_regex};
+
+    #[test]
+    fn accepted_patterns() {
+        for pattern in [
+            "^abc$",
+            "a{2,3}",
+            "(a|b)+",
+            "(ab)+c*",
+            "[a-z]+",
+            "[)]",
+            "([*])+",
+            "([)(])+",
+            "[]a]+",
+            "[^]a]*",
+            r"([\]+])+",
+            "[[:digit:]]+",
+            "([[:alpha:]])+",
+            r"\(a+\)+",
+        ] {
+            assert_eq!(validate_regex(pattern), Ok(()), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn nested_quantifiers() {
+        for pattern in [
+            "(a+)+",
+            "(a*)*",
+            "([a-z]+)*",
+            "((ab)+)?",
+            "(a{2})+",
+            "([)]+)+",
+        ] {
+            assert_eq!(
+                validate_regex(pattern),
+                Err("regex nested quantifiers are not allowed".to_string()),
+                "{}",
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn backreferences() {
+        assert_eq!(
+            validate_regex(r"(a)\1"),
+            Err("regex backreferences are not allowed".to_string())
+        );
+    }
+
+    #[test]
+    fn length() {
+        let expected = Err(format!(
+            "regex must be between 1 and {} characters",
+            MAX_REGEX_LENGTH
+        ));
+
+        assert_eq!(validate_regex(""), expected);
+        assert_eq!(validate_regex(&"a".repeat(MAX_REGEX_LENGTH + 1)), expected);
+        assert_eq!(validate_regex(&"é".repeat(MAX_REGEX_LENGTH)), Ok(()));
+    }
 }
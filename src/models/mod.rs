@@ -1,6 +1,11 @@
+pub mod analysers;
+pub mod backfill;
+pub mod expression;
 pub mod filters;
 pub mod nlp;
+pub mod pagination;
 pub mod properties;
 pub mod requests;
 pub mod responses;
+pub mod similarity;
 pub mod state;
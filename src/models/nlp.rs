@@ -1,17 +1,151 @@
+use chrono::SecondsFormat;
 use serde::Serialize;
 use utoipa::ToSchema;
 
-use crate::models::properties::AnalysedString;
+use crate::{
+    models::properties::MatchedString,
+    utils::nlp::{Language, LoadedVocabulary, Vocabulary, VocabularyStore},
+};
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct NlpResponse {
-    pub data: Vec<AnalysedString>,
+    pub data: Vec<MatchedString>,
     pub count: usize,
     pub interpreted_query: InterpretedQuery,
+    /// The parsed filters as an English sentence, which parses back to the same filters.
+    pub description: String,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+    pub total: Option<i64>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct InterpretedQuery {
     pub original: String,
+    /// The language the query was read in: the `language` asked for, or the one detected.
+    pub language: Language,
     pub parsed_filters: serde_json::Value,
+    /// How sure the parser is of `parsed_filters` as a whole, 0 to 1: the product of each
+    /// filter's confidence, lowered for every unused token and spelling correction.
+    pub confidence: f32,
+    /// Each phrase that became a filter or sort, in query order.
+    pub filters: Vec<InterpretedFilter>,
+    /// Other ways the query could be read, most likely first.
+    pub alternatives: Vec<AlternativeInterpretation>,
+    /// Tokens the parser turned into filters.
+    pub consumed_tokens: Vec<QueryToken>,
+    /// Tokens that matched no grammar rule, e.g. "strings" or "all".
+    pub ignored_tokens: Vec<QueryToken>,
+    /// Ignored tokens that are not filler words, e.g. "long" in "long strings"; the query
+    /// probably meant something the parser did not understand.
+    pub unused_tokens: Vec<QueryToken>,
+    /// Misspelt keywords the parser read as the closest one, e.g. "shorer" as "shorter".
+    pub corrections: Vec<Correction>,
+}
+
+/// One phrase of the query and the filters read from it.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InterpretedFilter {
+    pub text: String,
+    pub span: TextSpan,
+    pub parsed_filters: serde_json::Value,
+    /// 1 unless the phrase could be read another way.
+    pub confidence: f32,
+    /// Why the phrase is ambiguous; each has an entry in `alternatives` when the other
+    /// reading still parses.
+    pub ambiguities: Vec<String>,
+}
+
+/// The whole query read with one ambiguous phrase taken the other way.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AlternativeInterpretation {
+    pub reason: String,
+    /// The ambiguous phrase.
+    pub span: TextSpan,
+    pub parsed_filters: serde_json::Value,
+    pub confidence: f32,
+}
+
+/// A word read as the keyword it is most likely a typo of.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Correction {
+    /// The word as typed.
+    pub text: String,
+    pub corrected: String,
+    pub span: TextSpan,
+    /// e.g. "interpreted 'shorer' as 'shorter'"
+    pub reason: String,
+}
+
+pub const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+pub const MAX_SUGGESTION_LIMIT: usize = 50;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuggestResponse {
+    pub prefix: String,
+    /// Most complete match with the typed words first.
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// A completion of a partially typed query.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Suggestion {
+    /// The prefix completed, with `N`, `M`, `X` and `DATE` standing for a number, a second
+    /// number, a character and a `YYYY-MM-DD` date, e.g. "strings longer than N characters".
+    pub text: String,
+    /// `text` with sample values filled in; it parses.
+    pub example: String,
+    /// What `example` filters for, as in `NlpResponse.description`.
+    pub description: String,
+}
+
+/// Character offsets into the original query, end exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct TextSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct QueryToken {
+    pub text: String,
+    pub span: TextSpan,
+}
+
+/// Why a natural language query could not be turned into filters, returned as the 400
+/// `details` so clients can underline `span`.
+#[derive(Debug, Clone, Serialize)]
+pub struct NlpError {
+    pub reason: String,
+    /// Part of the query the error refers to; `None` for conflicting filters.
+    pub span: Option<TextSpan>,
+    pub consumed_tokens: Vec<QueryToken>,
+    pub ignored_tokens: Vec<QueryToken>,
+}
+
+impl NlpError {
+    pub fn is_conflict(&self) -> bool {
+        self.reason.contains("Conflicting")
+    }
+}
+
+/// The vocabulary the natural language parser is using.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VocabularyResponse {
+    /// File the vocabulary was loaded from; `null` for the built-in one.
+    pub source: Option<String>,
+    pub loaded_at: String,
+    pub vocabulary: Vocabulary,
+}
+
+impl VocabularyResponse {
+    pub fn new(store: &VocabularyStore, loaded: LoadedVocabulary) -> Self {
+        Self {
+            source: store.path().map(|path| path.display().to_string()),
+            loaded_at: loaded
+                .loaded_at
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            vocabulary: (*loaded.vocabulary).clone(),
+        }
+    }
 }
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::models::{filters::SortOrder, properties::MatchedString};
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 100;
+pub const MAX_PAGE_LIMIT: i64 = 1000;
+
+/// Length of the HMAC-SHA256 tag appended to every cursor.
+const SIGNATURE_LENGTH: usize = 32;
+
+/// Position after the last row of a page: its sort key and `id`, plus the ordering the
+/// page was produced with so a cursor cannot be replayed against a different sort.
+///
+/// Clients see it as an opaque hex token; its contents are not part of the API. The token is
+/// signed, so a client cannot edit `returned` to get past `max_results`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PageCursor {
+    pub column: String,
+    pub order: SortOrder,
+    pub key: CursorKey,
+    pub id: String,
+    /// Rows served before this cursor, so `max_results` holds across pages.
+    #[serde(default)]
+    pub returned: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum CursorKey {
+    /// `created_at` in microseconds since the Unix epoch.
+    Timestamp(i64),
+    Integer(i32),
+    Text(String),
+    /// Full-text search `rank`.
+    Real(f32),
+}
+
+impl CursorKey {
+    pub fn timestamp(created_at: DateTime<Utc>) -> Self {
+        CursorKey::Timestamp(created_at.timestamp_micros())
+    }
+}
+
+impl PageCursor {
+    pub fn encode(&self, secret: &[u8]) -> String {
+        let mut bytes = serde_json::to_vec(self).unwrap();
+        let signature = signer(secret, &bytes).finalize().into_bytes();
+        bytes.extend_from_slice(&signature);
+
+        hex::encode(bytes)
+    }
+
+    /// Decodes a token, checks it was signed with `secret` and issued for the same sort
+    /// column and order.
+    pub fn decode(
+        token: &str,
+        secret: &[u8],
+        column: &str,
+        order: SortOrder,
+    ) -> Result<Self, String> {
+        let cursor = hex::decode(token)
+            .ok()
+            .filter(|bytes| bytes.len() > SIGNATURE_LENGTH)
+            .and_then(|bytes| {
+                let (payload, signature) = bytes.split_at(bytes.len() - SIGNATURE_LENGTH);
+                signer(secret, payload).verify_slice(signature).ok()?;
+                serde_json::from_slice::<PageCursor>(payload).ok()
+            })
+            .ok_or_else(|| "Invalid cursor".to_string())?;
+
+        if cursor.column != column || cursor.order != order {
+            return Err("Cursor does not match the requested sort order".to_string());
+        }
+
+        let key_matches_column = match cursor.key {
+            CursorKey::Timestamp(micros) => {
+                column == "created_at" && DateTime::from_timestamp_micros(micros).is_some()
+            }
+            CursorKey::Text(_) => column == "value",
+            CursorKey::Real(rank) => column == "rank" && rank.is_finite(),
+            CursorKey::Integer(_) => !matches!(column, "created_at" | "value" | "rank"),
+        };
+
+        if !key_matches_column {
+            return Err("Invalid cursor".to_string());
+        }
+
+        Ok(cursor)
+    }
+}
+
+fn signer(secret: &[u8], payload: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    mac
+}
+
+pub struct StringPage {
+    pub data: Vec<MatchedString>,
+    pub next_cursor: Option<PageCursor>,
+    pub has_more: bool,
+    pub total: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CursorKey, PageCursor};
+    use crate::models::filters::SortOrder;
+
+    const SECRET: &[u8] = b"test secret";
+
+    fn cursor() -> PageCursor {
+        PageCursor {
+            column: "length".to_string(),
+            order: SortOrder::Asc,
+            key: CursorKey::Integer(12),
+            id: "abc".to_string(),
+            returned: 100,
+        }
+    }
+
+    fn decode(token: &str) -> Result<PageCursor, String> {
+        PageCursor::decode(token, SECRET, "length", SortOrder::Asc)
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(decode(&cursor().encode(SECRET)), Ok(cursor()));
+    }
+
+    #[test]
+    fn malformed_tokens() {
+        let invalid = Err("Invalid cursor".to_string());
+
+        assert_eq!(decode(""), invalid);
+        assert_eq!(decode("not hex"), invalid);
+        assert_eq!(decode("abc"), invalid);
+        assert_eq!(decode(&"00".repeat(32)), invalid);
+        assert_eq!(decode(&hex::encode(b"{\"column\":\"length\"}")), invalid);
+    }
+
+    #[test]
+    fn tampered_tokens() {
+        let invalid = Err("Invalid cursor".to_string());
+        let token = cursor().encode(SECRET);
+
+        // Resetting `returned` and keeping the old signature.
+        let signature = &token[token.len() - 64..];
+        let forged = PageCursor {
+            returned: 0,
+            ..cursor()
+        };
+        let payload = hex::encode(serde_json::to_vec(&forged).unwrap());
+        assert_eq!(decode(&format!("{}{}", payload, signature)), invalid);
+
+        // The same cursor without a signature, as tokens used to be issued.
+        assert_eq!(decode(&payload), invalid);
+
+        // Signed with another key.
+        assert_eq!(decode(&forged.encode(b"other secret")), invalid);
+
+        // Any flipped byte.
+        let mut bytes = hex::decode(&token).unwrap();
+        bytes[10] ^= 1;
+        assert_eq!(decode(&hex::encode(&bytes)), invalid);
+    }
+
+    #[test]
+    fn sort_mismatch() {
+        let token = cursor().encode(SECRET);
+
+        assert_eq!(
+            PageCursor::decode(&token, SECRET, "length", SortOrder::Desc),
+            Err("Cursor does not match the requested sort order".to_string())
+        );
+        assert_eq!(
+            PageCursor::decode(&token, SECRET, "word_count", SortOrder::Asc),
+            Err("Cursor does not match the requested sort order".to_string())
+        );
+    }
+}
@@ -3,14 +3,60 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, ToSchema)]
 pub struct StringProperties {
+    /// Number of extended grapheme clusters (user-perceived characters).
     pub length: i32,
+    /// Size of the UTF-8 encoding in bytes.
+    #[serde(default)]
+    pub byte_length: i32,
+    /// Number of Unicode scalar values.
+    #[serde(default)]
+    pub codepoint_length: i32,
+    /// Case-insensitive palindrome where whitespace and punctuation count.
     pub is_palindrome: bool,
+    /// Palindrome once everything but letters and digits is ignored.
+    #[serde(default)]
+    pub is_loose_palindrome: bool,
+    /// Palindrome after NFKD normalisation, compared grapheme by grapheme.
+    #[serde(default)]
+    pub is_normalised_palindrome: bool,
     pub unique_characters: i32,
     pub word_count: i32,
     pub sha256_hash: String,
     pub character_frequency_map: HashMap<String, i32>,
+    /// Decimal digits in any script.
+    #[serde(default)]
+    pub digit_count: i32,
+    #[serde(default)]
+    pub uppercase_count: i32,
+    #[serde(default)]
+    pub lowercase_count: i32,
+    #[serde(default)]
+    pub punctuation_count: i32,
+    /// Spaces, tabs, line breaks and other Unicode whitespace.
+    #[serde(default)]
+    pub whitespace_count: i32,
+    /// Currency, maths and other symbols that are not shown as emoji.
+    #[serde(default)]
+    pub symbol_count: i32,
+    /// Graphemes shown as emoji, so a flag or a family counts once.
+    #[serde(default)]
+    pub emoji_count: i32,
+    /// Letters a, e, i, o and u, accented or not.
+    #[serde(default)]
+    pub vowel_count: i32,
+    /// Latin letters other than the vowels, "y" included.
+    #[serde(default)]
+    pub consonant_count: i32,
+    /// Share of the vowels among the vowels and consonants, 0 without either.
+    #[serde(default)]
+    pub vowel_ratio: f64,
+    #[serde(default)]
+    pub longest_consonant_run: i32,
+    /// Version of each analyser that produced these properties, keyed by analyser name.
+    #[serde(default)]
+    pub analyser_versions: HashMap<String, i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
@@ -20,3 +66,40 @@ pub struct AnalysedString {
     pub properties: StringProperties,
     pub created_at: String,
 }
+
+/// A listed string, plus its full-text search rank and highlighted snippet when the
+/// listing was searched with `q`.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct MatchedString {
+    #[serde(flatten)]
+    pub analysed_string: AnalysedString,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<f32>,
+    /// Matching fragments of the value with search terms wrapped in `<mark>` tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StringProperties;
+
+    #[test]
+    fn entries_without_palindrome_modes_decode() {
+        let cached = r#"{
+            "length": 7,
+            "is_palindrome": true,
+            "unique_characters": 4,
+            "word_count": 1,
+            "sha256_hash": "abc",
+            "character_frequency_map": {"r": 2, "a": 2, "c": 2, "e": 1}
+        }"#;
+
+        let properties: StringProperties = serde_json::from_str(cached).unwrap();
+
+        assert!(properties.is_palindrome);
+        assert!(!properties.is_loose_palindrome);
+        assert!(!properties.is_normalised_palindrome);
+        assert_eq!(properties.byte_length, 0);
+    }
+}
@@ -1,6 +1,8 @@
 use serde::Deserialize;
 use utoipa::ToSchema;
 
+use crate::utils::nlp::Language;
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateStringRequest {
     pub value: String,
@@ -9,4 +11,18 @@ pub struct CreateStringRequest {
 #[derive(Debug, Deserialize)]
 pub struct NlpQuery {
     pub query: String,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
+    /// Return only `interpreted_query`, without querying strings (default false).
+    pub interpret_only: Option<bool>,
+    /// Language of `query`; detected from its words when absent.
+    pub language: Option<Language>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestQuery {
+    /// The query typed so far; a last word not followed by a space may be unfinished.
+    pub prefix: String,
+    pub limit: Option<usize>,
 }
@@ -2,13 +2,20 @@ use serde::Serialize;
 use serde_json::Value;
 use utoipa::ToSchema;
 
-use crate::models::properties::AnalysedString;
+use crate::models::properties::MatchedString;
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct GetStringsResponse {
-    pub data: Vec<AnalysedString>,
+    pub data: Vec<MatchedString>,
     pub count: usize,
     pub filters_applied: Value,
+    /// `filters_applied` as an English sentence, e.g. "palindromes longer than 5 characters".
+    pub description: String,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+    /// Number of rows matching the filters, only computed when `include_total=true`.
+    pub total: Option<i64>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -23,23 +30,26 @@ impl ApiErrorResponse {
     pub fn invalid_input(message: String, details: Option<Value>) -> Self {
         Self {
             code: "INVALID_INPUT".to_string(),
-            message: message,
-            details: match details {
-                Some(val) => Some(val),
-                None => None,
-            },
+            message,
+            details,
             status: 400,
         }
     }
 
+    pub fn unauthorized(message: String, details: Option<Value>) -> Self {
+        Self {
+            code: "UNAUTHORIZED".to_string(),
+            message,
+            details,
+            status: 401,
+        }
+    }
+
     pub fn not_found(message: String, details: Option<Value>) -> Self {
         Self {
             code: "NOT_FOUND".to_string(),
-            message: message,
-            details: match details {
-                Some(val) => Some(val),
-                None => None,
-            },
+            message,
+            details,
             status: 404,
         }
     }
@@ -47,11 +57,8 @@ impl ApiErrorResponse {
     pub fn conflict(message: String, details: Option<Value>) -> Self {
         Self {
             code: "CONFLICT".to_string(),
-            message: message,
-            details: match details {
-                Some(val) => Some(val),
-                None => None,
-            },
+            message,
+            details,
             status: 409,
         }
     }
@@ -59,11 +66,8 @@ impl ApiErrorResponse {
     pub fn validaton_error(message: String, details: Option<Value>) -> Self {
         Self {
             code: "VALIDATION_ERROR".to_string(),
-            message: message,
-            details: match details {
-                Some(val) => Some(val),
-                None => None,
-            },
+            message,
+            details,
             status: 422,
         }
     }
@@ -71,11 +75,8 @@ impl ApiErrorResponse {
     pub fn throttled(message: String, details: Option<Value>) -> Self {
         Self {
             code: "RATE_LIMIT_EXCEEDED".to_string(),
-            message: message,
-            details: match details {
-                Some(val) => Some(val),
-                None => None,
-            },
+            message,
+            details,
             status: 429,
         }
     }
@@ -83,11 +84,8 @@ impl ApiErrorResponse {
     pub fn internal_error(message: String, details: Option<Value>) -> Self {
         Self {
             code: "INTERNAL_SERVER_ERROR".to_string(),
-            message: message,
-            details: match details {
-                Some(val) => Some(val),
-                None => None,
-            },
+            message,
+            details,
             status: 500,
         }
     }
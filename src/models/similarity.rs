@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::properties::AnalysedString;
+
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.3;
+pub const DEFAULT_SIMILAR_LIMIT: i64 = 10;
+pub const MAX_SIMILAR_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarQuery {
+    pub value: String,
+    /// Minimum trigram similarity between 0 and 1.
+    pub threshold: Option<f32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimilarString {
+    pub analysed_string: AnalysedString,
+    /// Trigram similarity to the searched value, between 0 and 1.
+    pub similarity: f32,
+    /// Levenshtein distance to the searched value.
+    pub edit_distance: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimilarStringsResponse {
+    pub data: Vec<SimilarString>,
+    pub count: usize,
+    pub value: String,
+    pub threshold: f32,
+}
@@ -1,7 +1,16 @@
-use crate::{cache::service::CacheService, db::repositories::StringRepository};
+use crate::{
+    cache::service::CacheService,
+    db::repositories::StringRepository,
+    utils::nlp::{NlpOptions, VocabularyStore},
+};
 
 #[derive(Clone)]
 pub struct AppState {
     pub repository: StringRepository,
     pub cache: CacheService,
+    pub admin_token: Option<String>,
+    /// Key that signs pagination cursors.
+    pub cursor_secret: Vec<u8>,
+    pub nlp_options: NlpOptions,
+    pub vocabulary: VocabularyStore,
 }
@@ -0,0 +1,154 @@
+use axum::{
+    Json,
+    extract::{State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+
+use crate::{
+    jobs::backfill::{BackfillStart, run_backfill, start_backfill},
+    models::{
+        backfill::{BackfillProgress, BackfillRequest},
+        nlp::VocabularyResponse,
+        responses::ApiErrorResponse,
+        state::AppState,
+    },
+};
+
+#[utoipa::path(
+    post,
+    path = "/admin/backfill",
+    request_body = BackfillRequest,
+    responses(
+        (status = 202, description = "Backfill started or resumed in the background", body = BackfillProgress),
+        (status = 401, description = "Missing or invalid admin token", body = ApiErrorResponse),
+        (status = 409, description = "A backfill is already running", body = ApiErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn start_backfill_job(
+    State(state): State<AppState>,
+    payload: Result<Json<BackfillRequest>, JsonRejection>,
+) -> impl IntoResponse {
+    let request = match payload {
+        Ok(Json(request)) => request,
+        Err(JsonRejection::MissingJsonContentType(_)) => BackfillRequest::default(),
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiErrorResponse::invalid_input(
+                    "Invalid backfill request body".to_string(),
+                    None,
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    match start_backfill(&state.repository, &state.cache, &request).await {
+        Ok(BackfillStart::Started(progress)) => {
+            let response = progress.clone();
+
+            tokio::spawn(async move {
+                let _ = run_backfill(
+                    &state.repository,
+                    &state.cache,
+                    progress,
+                    request.batch_size,
+                )
+                .await;
+            });
+
+            (StatusCode::ACCEPTED, Json(response)).into_response()
+        }
+        Ok(BackfillStart::AlreadyRunning(progress)) => (
+            StatusCode::CONFLICT,
+            Json(ApiErrorResponse::conflict(
+                "A backfill is already running".to_string(),
+                serde_json::to_value(progress).ok(),
+            )),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Backfill start failed: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiErrorResponse::internal_error(
+                    "A server error occurred. Try again later".to_string(),
+                    None,
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/backfill",
+    responses(
+        (status = 200, description = "Progress of the current or last backfill", body = BackfillProgress),
+        (status = 401, description = "Missing or invalid admin token", body = ApiErrorResponse),
+        (status = 404, description = "No backfill has been run", body = ApiErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn get_backfill_progress(State(state): State<AppState>) -> impl IntoResponse {
+    match state.cache.get_backfill_progress().await {
+        Ok(Some(progress)) => (StatusCode::OK, Json(progress)).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiErrorResponse::not_found(
+                "No backfill has been run".to_string(),
+                None,
+            )),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Backfill progress retrieval failed: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiErrorResponse::internal_error(
+                    "A server error occurred. Try again later".to_string(),
+                    None,
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/nlp/vocabulary/reload",
+    responses(
+        (status = 200, description = "Vocabulary re-read from NLP_VOCABULARY_PATH", body = VocabularyResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ApiErrorResponse),
+        (status = 422, description = "The file is invalid; the previous vocabulary stays in use", body = ApiErrorResponse)
+    ),
+    tag = "Admin"
+)]
+pub async fn reload_nlp_vocabulary(State(state): State<AppState>) -> impl IntoResponse {
+    match state.vocabulary.reload() {
+        Ok(loaded) => {
+            tracing::info!("NLP vocabulary reloaded");
+            (
+                StatusCode::OK,
+                Json(VocabularyResponse::new(&state.vocabulary, loaded)),
+            )
+                .into_response()
+        }
+        Err(reason) => {
+            tracing::warn!("NLP vocabulary reload failed: {}", reason);
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ApiErrorResponse::validaton_error(
+                    "Invalid vocabulary file; the previous vocabulary is still in use".to_string(),
+                    Some(json!({ "reason": reason })),
+                )),
+            )
+                .into_response()
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::{
+    models::{
+        analysers::{AnalyserInfo, AnalysersResponse},
+        responses::ApiErrorResponse,
+        state::AppState,
+    },
+    utils::analyser::registry::registry,
+};
+
+#[utoipa::path(
+    get,
+    path = "/analysers",
+    responses(
+        (status = 200, description = "Registered analysers and how many stored strings they have outdated", body = AnalysersResponse)
+    ),
+    tag = "Analysers"
+)]
+pub async fn get_analysers(State(state): State<AppState>) -> impl IntoResponse {
+    let registry = registry();
+
+    match state.repository.count_outdated(&registry.versions()).await {
+        Ok(outdated_count) => {
+            let analysers = registry
+                .analysers()
+                .map(|analyser| AnalyserInfo {
+                    name: analyser.name().to_string(),
+                    version: analyser.version(),
+                })
+                .collect();
+
+            (
+                StatusCode::OK,
+                Json(AnalysersResponse {
+                    analysers,
+                    outdated_count,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!("Outdated string count failed: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiErrorResponse::internal_error(
+                    "A server error occurred. Try again later".to_string(),
+                    None,
+                )),
+            )
+                .into_response()
+        }
+    }
+}
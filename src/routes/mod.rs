@@ -1 +1,3 @@
+pub mod admin;
+pub mod analysers;
 pub mod strings;
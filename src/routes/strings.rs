@@ -10,15 +10,26 @@ use serde_json::{Value, json};
 use crate::{
     models::{
         filters::StringFilters,
-        nlp::{InterpretedQuery, NlpResponse},
+        nlp::{
+            DEFAULT_SUGGESTION_LIMIT, InterpretedQuery, MAX_SUGGESTION_LIMIT, NlpResponse,
+            SuggestResponse, VocabularyResponse,
+        },
+        pagination::PageCursor,
         properties::AnalysedString,
-        requests::{CreateStringRequest, NlpQuery},
+        requests::{CreateStringRequest, NlpQuery, SuggestQuery},
         responses::{ApiErrorResponse, GetStringsResponse},
+        similarity::{
+            DEFAULT_SIMILAR_LIMIT, DEFAULT_SIMILARITY_THRESHOLD, MAX_SIMILAR_LIMIT, SimilarQuery,
+            SimilarString, SimilarStringsResponse,
+        },
         state::AppState,
     },
     utils::{
         analyser::{analyse_string, compute_sha256},
-        nlp::parse_natural_language,
+        nlp::{
+            Language, NlpOptions, describe_filters, parse_natural_language, suggest_completions,
+        },
+        similarity::edit_distance,
     },
 };
 
@@ -142,7 +153,7 @@ pub async fn get_string(
 ) -> impl IntoResponse {
     let normalised_string_value = string_value.trim();
 
-    let id = compute_sha256(&normalised_string_value);
+    let id = compute_sha256(normalised_string_value);
 
     if let Ok(Some(analysed_string_cache)) = state.cache.get(&id).await {
         return (StatusCode::OK, Json(analysed_string_cache)).into_response();
@@ -150,7 +161,7 @@ pub async fn get_string(
 
     match state
         .repository
-        .get_by_value(&normalised_string_value)
+        .get_by_value(normalised_string_value)
         .await
     {
         Ok(Some(analysed_string)) => {
@@ -189,14 +200,76 @@ pub async fn get_string(
     path = "/strings",
     params(
         ("is_palindrome" = Option<bool>, Query, description = "Filter by palindrome status"),
+        ("palindrome_mode" = Option<String>, Query, description = "Palindrome check for is_palindrome: strict (default), loose or normalised"),
+        ("length" = Option<i32>, Query, description = "Exact string length"),
         ("min_length" = Option<i32>, Query, description = "Minimum string length"),
         ("max_length" = Option<i32>, Query, description = "Maximum string length"),
+        ("length_unit" = Option<String>, Query, description = "Length metric for length/min_length/max_length: bytes, codepoints or graphemes (default)"),
         ("word_count" = Option<i32>, Query, description = "Exact word count"),
-        ("contains_character" = Option<String>, Query, description = "Filter by character presence")
+        ("min_word_count" = Option<i32>, Query, description = "Minimum word count"),
+        ("max_word_count" = Option<i32>, Query, description = "Maximum word count"),
+        ("unique_characters" = Option<i32>, Query, description = "Exact unique character count"),
+        ("min_unique_characters" = Option<i32>, Query, description = "Minimum unique character count"),
+        ("max_unique_characters" = Option<i32>, Query, description = "Maximum unique character count"),
+        ("digit_count" = Option<i32>, Query, description = "Exact digit count"),
+        ("min_digit_count" = Option<i32>, Query, description = "Minimum digit count"),
+        ("max_digit_count" = Option<i32>, Query, description = "Maximum digit count"),
+        ("uppercase_count" = Option<i32>, Query, description = "Exact uppercase letter count"),
+        ("min_uppercase_count" = Option<i32>, Query, description = "Minimum uppercase letter count"),
+        ("max_uppercase_count" = Option<i32>, Query, description = "Maximum uppercase letter count"),
+        ("lowercase_count" = Option<i32>, Query, description = "Exact lowercase letter count"),
+        ("min_lowercase_count" = Option<i32>, Query, description = "Minimum lowercase letter count"),
+        ("max_lowercase_count" = Option<i32>, Query, description = "Maximum lowercase letter count"),
+        ("punctuation_count" = Option<i32>, Query, description = "Exact punctuation mark count"),
+        ("min_punctuation_count" = Option<i32>, Query, description = "Minimum punctuation mark count"),
+        ("max_punctuation_count" = Option<i32>, Query, description = "Maximum punctuation mark count"),
+        ("whitespace_count" = Option<i32>, Query, description = "Exact whitespace character count"),
+        ("min_whitespace_count" = Option<i32>, Query, description = "Minimum whitespace character count"),
+        ("max_whitespace_count" = Option<i32>, Query, description = "Maximum whitespace character count"),
+        ("symbol_count" = Option<i32>, Query, description = "Exact symbol count"),
+        ("min_symbol_count" = Option<i32>, Query, description = "Minimum symbol count"),
+        ("max_symbol_count" = Option<i32>, Query, description = "Maximum symbol count"),
+        ("emoji_count" = Option<i32>, Query, description = "Exact emoji count"),
+        ("min_emoji_count" = Option<i32>, Query, description = "Minimum emoji count"),
+        ("max_emoji_count" = Option<i32>, Query, description = "Maximum emoji count"),
+        ("vowel_count" = Option<i32>, Query, description = "Exact vowel count (a, e, i, o, u, accented or not)"),
+        ("min_vowel_count" = Option<i32>, Query, description = "Minimum vowel count"),
+        ("max_vowel_count" = Option<i32>, Query, description = "Maximum vowel count"),
+        ("consonant_count" = Option<i32>, Query, description = "Exact consonant count (other Latin letters, including y)"),
+        ("min_consonant_count" = Option<i32>, Query, description = "Minimum consonant count"),
+        ("max_consonant_count" = Option<i32>, Query, description = "Maximum consonant count"),
+        ("min_vowel_ratio" = Option<f64>, Query, description = "Minimum share of vowels among vowels and consonants, between 0 and 1"),
+        ("max_vowel_ratio" = Option<f64>, Query, description = "Maximum share of vowels among vowels and consonants, between 0 and 1"),
+        ("longest_consonant_run" = Option<i32>, Query, description = "Exact length of the longest run of consecutive consonants"),
+        ("min_longest_consonant_run" = Option<i32>, Query, description = "Minimum longest consonant run"),
+        ("max_longest_consonant_run" = Option<i32>, Query, description = "Maximum longest consonant run"),
+        ("contains_character" = Option<String>, Query, description = "Filter by character presence (single character)"),
+        ("contains_all" = Option<String>, Query, description = "Characters that must all occur, e.g. abc"),
+        ("contains_any" = Option<String>, Query, description = "Characters of which at least one must occur"),
+        ("excludes" = Option<String>, Query, description = "Characters that must not occur"),
+        ("min_char_count" = Option<String>, Query, description = "Minimum occurrences per character, e.g. e:3,l:2"),
+        ("max_char_count" = Option<String>, Query, description = "Maximum occurrences per character, e.g. z:1"),
+        ("starts_with" = Option<String>, Query, description = "Value prefix"),
+        ("ends_with" = Option<String>, Query, description = "Value suffix"),
+        ("contains_substring" = Option<String>, Query, description = "Substring of the value"),
+        ("regex" = Option<String>, Query, description = "Postgres regular expression (max 256 characters, no backreferences or nested quantifiers)"),
+        ("case_insensitive" = Option<bool>, Query, description = "Case-insensitive starts_with/ends_with/contains_substring/regex (default false)"),
+        ("created_after" = Option<String>, Query, description = "Created at or after this RFC 3339 timestamp or YYYY-MM-DD date (midnight UTC)"),
+        ("created_before" = Option<String>, Query, description = "Created before this RFC 3339 timestamp or YYYY-MM-DD date (midnight UTC)"),
+        ("filter" = Option<String>, Query, description = "Boolean filter expression, e.g. (is_palindrome:true OR word_count:1) AND NOT contains:z"),
+        ("q" = Option<String>, Query, description = "Full-text search query, e.g. \"quick fox\" or -slow (max 256 characters)"),
+        ("language" = Option<String>, Query, description = "Stemming for q: english (default), french, spanish or simple"),
+        ("sort" = Option<String>, Query, description = "Sort field: created_at (default), relevance (default with q), length, word_count, unique_characters or value"),
+        ("order" = Option<String>, Query, description = "asc or desc (default desc for created_at and relevance, asc otherwise)"),
+        ("limit" = Option<i64>, Query, description = "Page size (default 100, max 1000)"),
+        ("max_results" = Option<i64>, Query, description = "At most this many matches in total, across pages"),
+        ("cursor" = Option<String>, Query, description = "next_cursor from the previous page"),
+        ("include_total" = Option<bool>, Query, description = "Also count all matching strings")
     ),
     responses(
         (status = 200, description = "List of strings matching filters", body = GetStringsResponse),
-        (status = 400, description = "Invalid query parameters", body = ApiErrorResponse)
+        (status = 400, description = "Invalid query parameters, filter expression, cursor or regular expression", body = ApiErrorResponse),
+        (status = 422, description = "Invalid or conflicting filters, or regular expression too expensive", body = ApiErrorResponse)
     ),
     tag = "Strings"
 )]
@@ -204,27 +277,91 @@ pub async fn get_all_strings(
     state: State<AppState>,
     query: Query<StringFilters>,
 ) -> impl IntoResponse {
-    let filters = query.0;
+    let mut filters = query.0;
 
-    match state.repository.filter(&filters).await {
-        Ok(data) => {
-            let count = data.len();
-            let mut filters_applied = serde_json::to_value(&filters).unwrap_or(json!({}));
+    if let Err(e) = filters.parse_expression() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiErrorResponse::invalid_input(
+                "Invalid filter expression".to_string(),
+                Some(json!(e)),
+            )),
+        )
+            .into_response();
+    }
 
-            if let Some(obj) = filters_applied.as_object_mut() {
-                obj.retain(|_, v| !v.is_null());
-            }
+    if let Err(e) = filters.validate() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiErrorResponse::validaton_error(
+                "Invalid or conflicting filters".to_string(),
+                Some(json!({ "reason": e })),
+            )),
+        )
+            .into_response();
+    }
+
+    let cursor = match filters
+        .cursor
+        .as_deref()
+        .map(|token| {
+            PageCursor::decode(
+                token,
+                &state.cursor_secret,
+                filters.sort_column(),
+                filters.sort_order(),
+            )
+        })
+        .transpose()
+    {
+        Ok(cursor) => cursor,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiErrorResponse::invalid_input(e, None)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.repository.filter(&filters, cursor.as_ref()).await {
+        Ok(page) => {
+            let count = page.data.len();
+            let filters_applied = filters.applied();
+            let description = describe_filters(&filters);
 
             (
                 StatusCode::OK,
                 Json(GetStringsResponse {
-                    data,
+                    data: page.data,
                     count,
                     filters_applied,
+                    description,
+                    next_cursor: page
+                        .next_cursor
+                        .map(|cursor| cursor.encode(&state.cursor_secret)),
+                    has_more: page.has_more,
+                    total: page.total,
                 }),
             )
                 .into_response()
         }
+        Err(e) if database_error_code(&e).as_deref() == Some(INVALID_REGULAR_EXPRESSION) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiErrorResponse::invalid_input(
+                "Invalid regular expression".to_string(),
+                None,
+            )),
+        )
+            .into_response(),
+        Err(e) if database_error_code(&e).as_deref() == Some(QUERY_CANCELED) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiErrorResponse::validaton_error(
+                "Regular expression took too long to evaluate".to_string(),
+                None,
+            )),
+        )
+            .into_response(),
         Err(e) => {
             tracing::error!("String filter-retrieval failed: {:?}", e);
             (
@@ -276,11 +413,11 @@ pub async fn delete_string(
 
     match state
         .repository
-        .delete_by_value(&normalised_string_value)
+        .delete_by_value(normalised_string_value)
         .await
     {
         Ok(true) => {
-            let id = compute_sha256(&normalised_string_value);
+            let id = compute_sha256(normalised_string_value);
 
             let cache_clone = state.cache.clone();
             tokio::spawn(async move {
@@ -316,11 +453,16 @@ pub async fn delete_string(
     get,
     path = "/strings/filter-by-natural-language",
     params(
-        ("query" = String, Query, description = "Natural language query string")
+        ("query" = String, Query, description = "Natural language query string"),
+        ("limit" = Option<i64>, Query, description = "Page size (default 100, max 1000)"),
+        ("cursor" = Option<String>, Query, description = "next_cursor from the previous page"),
+        ("include_total" = Option<bool>, Query, description = "Also count all matching strings"),
+        ("interpret_only" = Option<bool>, Query, description = "Return only interpreted_query, without querying strings"),
+        ("language" = Option<Language>, Query, description = "Language of the query: en, fr or es (detected when absent)")
     ),
     responses(
         (status = 200, description = "Strings matching natural language query", body = NlpResponse),
-        (status = 400, description = "Unable to parse query", body = ApiErrorResponse),
+        (status = 400, description = "Unable to parse query (details carry the reason, error span and consumed/ignored tokens) or invalid cursor", body = ApiErrorResponse),
         (status = 422, description = "Conflicting filters detected", body = ApiErrorResponse)
     ),
     tag = "Strings"
@@ -329,10 +471,15 @@ pub async fn get_by_natural_language(
     State(state): State<AppState>,
     Query(query): Query<NlpQuery>,
 ) -> impl IntoResponse {
-    let parsed_query = match parse_natural_language(&query.query) {
+    let vocabulary = state.vocabulary.current().vocabulary;
+    let options = NlpOptions {
+        language: query.language,
+        ..state.nlp_options
+    };
+    let mut parsed_query = match parse_natural_language(&query.query, &options, &vocabulary) {
         Ok(query) => query,
         Err(e) => {
-            if e.contains("Conflicting") {
+            if e.is_conflict() {
                 return (
                     StatusCode::UNPROCESSABLE_ENTITY,
                     Json(ApiErrorResponse::conflict(
@@ -346,7 +493,7 @@ pub async fn get_by_natural_language(
                     StatusCode::BAD_REQUEST,
                     Json(ApiErrorResponse::invalid_input(
                         "Unable to parse natural language query".to_string(),
-                        None,
+                        Some(json!(e)),
                     )),
                 )
                     .into_response();
@@ -354,25 +501,82 @@ pub async fn get_by_natural_language(
         }
     };
 
-    match state.repository.filter(&parsed_query.filters).await {
-        Ok(data) => {
-            let count = data.len();
-            let mut filters_applied =
-                serde_json::to_value(&parsed_query.filters).unwrap_or(json!({}));
+    let interpreted_query = InterpretedQuery {
+        original: parsed_query.original,
+        language: parsed_query.language,
+        parsed_filters: parsed_query.filters.applied(),
+        confidence: parsed_query.confidence,
+        filters: parsed_query.interpreted_filters,
+        alternatives: parsed_query.alternatives,
+        consumed_tokens: parsed_query.consumed_tokens,
+        ignored_tokens: parsed_query.ignored_tokens,
+        unused_tokens: parsed_query.unused_tokens,
+        corrections: parsed_query.corrections,
+    };
+    let description = describe_filters(&parsed_query.filters);
 
-            if let Some(obj) = filters_applied.as_object_mut() {
-                obj.retain(|_, v| !v.is_null());
-            }
+    if query.interpret_only.unwrap_or(false) {
+        return (
+            StatusCode::OK,
+            Json(NlpResponse {
+                data: Vec::new(),
+                count: 0,
+                interpreted_query,
+                description,
+                next_cursor: None,
+                has_more: false,
+                total: None,
+            }),
+        )
+            .into_response();
+    }
+
+    parsed_query.filters.limit = query.limit;
+    parsed_query.filters.include_total = query.include_total;
+
+    let cursor = match query
+        .cursor
+        .as_deref()
+        .map(|token| {
+            PageCursor::decode(
+                token,
+                &state.cursor_secret,
+                parsed_query.filters.sort_column(),
+                parsed_query.filters.sort_order(),
+            )
+        })
+        .transpose()
+    {
+        Ok(cursor) => cursor,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiErrorResponse::invalid_input(e, None)),
+            )
+                .into_response();
+        }
+    };
+
+    match state
+        .repository
+        .filter(&parsed_query.filters, cursor.as_ref())
+        .await
+    {
+        Ok(page) => {
+            let count = page.data.len();
 
             (
                 StatusCode::OK,
                 Json(NlpResponse {
-                    data,
+                    data: page.data,
                     count,
-                    interpreted_query: InterpretedQuery {
-                        original: parsed_query.original,
-                        parsed_filters: filters_applied,
-                    },
+                    interpreted_query,
+                    description,
+                    next_cursor: page
+                        .next_cursor
+                        .map(|cursor| cursor.encode(&state.cursor_secret)),
+                    has_more: page.has_more,
+                    total: page.total,
                 }),
             )
                 .into_response()
@@ -390,3 +594,181 @@ pub async fn get_by_natural_language(
         }
     }
 }
+
+#[utoipa::path(
+    get,
+    path = "/strings/filter-by-natural-language/vocabulary",
+    responses(
+        (status = 200, description = "Words the natural language parser recognises, by filter", body = VocabularyResponse)
+    ),
+    tag = "Strings"
+)]
+pub async fn get_nlp_vocabulary(State(state): State<AppState>) -> impl IntoResponse {
+    let response = VocabularyResponse::new(&state.vocabulary, state.vocabulary.current());
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/strings/filter-by-natural-language/suggest",
+    params(
+        ("prefix" = String, Query, description = "The query typed so far"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of suggestions (default 10, max 50)")
+    ),
+    responses(
+        (status = 200, description = "Completions of the prefix that parse", body = SuggestResponse),
+        (status = 400, description = "Missing prefix", body = ApiErrorResponse)
+    ),
+    tag = "Strings"
+)]
+pub async fn get_nlp_suggestions(
+    State(state): State<AppState>,
+    query_result: Result<Query<SuggestQuery>, QueryRejection>,
+) -> impl IntoResponse {
+    let Ok(Query(query)) = query_result else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiErrorResponse::invalid_input(
+                "Invalid or missing \"prefix\" query parameter".to_string(),
+                None,
+            )),
+        )
+            .into_response();
+    };
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SUGGESTION_LIMIT)
+        .clamp(1, MAX_SUGGESTION_LIMIT);
+    let vocabulary = state.vocabulary.current().vocabulary;
+    let suggestions = suggest_completions(&query.prefix, &state.nlp_options, &vocabulary, limit);
+
+    let response = SuggestResponse {
+        prefix: query.prefix,
+        suggestions,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/strings/similar",
+    params(
+        ("value" = String, Query, description = "Value to find similar strings for"),
+        ("threshold" = Option<f32>, Query, description = "Minimum trigram similarity between 0 and 1 (default 0.3)"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of matches (default 10, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Stored strings ranked by similarity", body = SimilarStringsResponse),
+        (status = 400, description = "Missing value or invalid threshold", body = ApiErrorResponse)
+    ),
+    tag = "Strings"
+)]
+pub async fn get_similar_strings(
+    State(state): State<AppState>,
+    query_result: Result<Query<SimilarQuery>, QueryRejection>,
+) -> impl IntoResponse {
+    let query = match query_result {
+        Ok(Query(query)) if !query.value.trim().is_empty() => query,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiErrorResponse::invalid_input(
+                    "Invalid or missing \"value\" query parameter".to_string(),
+                    None,
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    let value = query.value.trim();
+    let threshold = query.threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SIMILAR_LIMIT)
+        .clamp(1, MAX_SIMILAR_LIMIT);
+
+    if !(0.0..=1.0).contains(&threshold) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiErrorResponse::invalid_input(
+                "threshold must be between 0 and 1".to_string(),
+                None,
+            )),
+        )
+            .into_response();
+    }
+
+    let cache_key = format!(
+        "similar:{}",
+        compute_sha256(&format!("{}|{}|{}", value, threshold, limit))
+    );
+
+    if let Ok(Some(cached)) = state
+        .cache
+        .get_query::<SimilarStringsResponse>(&cache_key)
+        .await
+    {
+        return (StatusCode::OK, Json(cached)).into_response();
+    }
+
+    match state.repository.find_similar(value, threshold, limit).await {
+        Ok(matches) => {
+            let mut data = matches
+                .into_iter()
+                .map(|(analysed_string, similarity)| SimilarString {
+                    edit_distance: edit_distance(&analysed_string.value, value),
+                    analysed_string,
+                    similarity,
+                })
+                .collect::<Vec<SimilarString>>();
+
+            data.sort_by(|a, b| {
+                b.similarity
+                    .total_cmp(&a.similarity)
+                    .then(a.edit_distance.cmp(&b.edit_distance))
+            });
+
+            let response = SimilarStringsResponse {
+                count: data.len(),
+                data,
+                value: value.to_string(),
+                threshold,
+            };
+
+            let cache_clone = state.cache.clone();
+            let response_clone = response.clone();
+
+            tokio::spawn(async move {
+                let _ = cache_clone.set_query(&cache_key, &response_clone).await;
+            });
+
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Similar string retrieval failed: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiErrorResponse::internal_error(
+                    "A server error occurred. Try again later".to_string(),
+                    None,
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// SQLSTATE raised by Postgres for a malformed regular expression.
+const INVALID_REGULAR_EXPRESSION: &str = "2201B";
+/// SQLSTATE raised when `statement_timeout` cancels a query.
+const QUERY_CANCELED: &str = "57014";
+
+fn database_error_code(e: &sqlx::Error) -> Option<String> {
+    e.as_database_error()
+        .and_then(|db_error| db_error.code())
+        .map(|code| code.into_owned())
+}
@@ -0,0 +1,39 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{models::properties::StringProperties, utils::analyser::Analyser};
+
+/// Case-insensitive unique character count and character frequency map.
+pub struct CharacterAnalyser;
+
+impl Analyser for CharacterAnalyser {
+    fn name(&self) -> &'static str {
+        "characters"
+    }
+
+    fn version(&self) -> i32 {
+        1
+    }
+
+    fn analyse(&self, input: &str, properties: &mut StringProperties) {
+        properties.unique_characters = get_unique_chars_count(input);
+        properties.character_frequency_map = get_char_frequency_map(input);
+    }
+}
+
+fn get_unique_chars_count(input: &str) -> i32 {
+    let mut chars = HashSet::new();
+    for char in input.to_lowercase().chars() {
+        chars.insert(char);
+    }
+
+    chars.len() as i32
+}
+
+fn get_char_frequency_map(input: &str) -> HashMap<String, i32> {
+    let mut frequency_map = HashMap::new();
+    for char in input.to_lowercase().chars() {
+        *frequency_map.entry(char.to_string()).or_insert(0) += 1;
+    }
+
+    frequency_map
+}
@@ -0,0 +1,123 @@
+use unicode_properties::{
+    EmojiStatus, GeneralCategory, GeneralCategoryGroup, UnicodeEmoji, UnicodeGeneralCategory,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{models::properties::StringProperties, utils::analyser::Analyser};
+
+/// Counts of digits, uppercase and lowercase letters, punctuation, whitespace, symbols and
+/// emoji. Emoji are counted per grapheme and their code points count towards nothing else.
+pub struct CharacterClassAnalyser;
+
+impl Analyser for CharacterClassAnalyser {
+    fn name(&self) -> &'static str {
+        "character_classes"
+    }
+
+    fn version(&self) -> i32 {
+        1
+    }
+
+    fn analyse(&self, input: &str, properties: &mut StringProperties) {
+        for grapheme in input.graphemes(true) {
+            if is_emoji(grapheme) {
+                properties.emoji_count += 1;
+                continue;
+            }
+
+            for c in grapheme.chars() {
+                let count = if c.is_whitespace() {
+                    &mut properties.whitespace_count
+                } else {
+                    match (c.general_category(), c.general_category_group()) {
+                        (GeneralCategory::DecimalNumber, _) => &mut properties.digit_count,
+                        (GeneralCategory::UppercaseLetter, _) => &mut properties.uppercase_count,
+                        (GeneralCategory::LowercaseLetter, _) => &mut properties.lowercase_count,
+                        (_, GeneralCategoryGroup::Punctuation) => &mut properties.punctuation_count,
+                        (_, GeneralCategoryGroup::Symbol) => &mut properties.symbol_count,
+                        _ => continue,
+                    }
+                };
+                *count += 1;
+            }
+        }
+    }
+}
+
+/// A grapheme shown as an emoji: one with a code point drawn as emoji by default (which
+/// covers flags and skin tones), or an emoji character asking for emoji presentation with
+/// U+FE0F, as in "❤️" and "1️⃣". A bare "❤" or digit is text.
+fn is_emoji(grapheme: &str) -> bool {
+    let presentation = grapheme.chars().any(|c| {
+        matches!(
+            c.emoji_status(),
+            EmojiStatus::EmojiPresentation
+                | EmojiStatus::EmojiPresentationAndModifierBase
+                | EmojiStatus::EmojiPresentationAndEmojiComponent
+                | EmojiStatus::EmojiPresentationAndModifierAndEmojiComponent
+        )
+    });
+
+    presentation
+        || (grapheme.contains('\u{FE0F}')
+            && grapheme.chars().next().is_some_and(|c| c.is_emoji_char()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CharacterClassAnalyser;
+    use crate::{models::properties::StringProperties, utils::analyser::Analyser};
+
+    /// Digit, uppercase, lowercase, punctuation, whitespace, symbol and emoji counts.
+    fn classes(input: &str) -> [i32; 7] {
+        let mut properties = StringProperties::default();
+        CharacterClassAnalyser.analyse(input, &mut properties);
+
+        [
+            properties.digit_count,
+            properties.uppercase_count,
+            properties.lowercase_count,
+            properties.punctuation_count,
+            properties.whitespace_count,
+            properties.symbol_count,
+            properties.emoji_count,
+        ]
+    }
+
+    #[test]
+    fn heart() {
+        assert_eq!(classes("❤"), [0, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(classes("❤\u{FE0F}"), [0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn keycap() {
+        assert_eq!(classes("1\u{FE0F}\u{20E3}"), [0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(classes("1"), [1, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn flag() {
+        assert_eq!(classes("🇫🇷"), [0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn skin_tone_and_zwj_sequences() {
+        assert_eq!(classes("👍🏽"), [0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(classes("👨🏽\u{200D}👩\u{200D}👧"), [0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(classes("😀 👍🏽"), [0, 0, 0, 0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn mixed_case_and_punctuation() {
+        assert_eq!(classes("Hello, World! 123"), [3, 2, 8, 2, 2, 0, 0]);
+        assert_eq!(classes("€5 + 3 = 8 ✓"), [3, 0, 0, 0, 5, 4, 0]);
+        assert_eq!(classes("ÉCOLE d'été"), [0, 5, 4, 1, 1, 0, 0]);
+        assert_eq!(classes("tab\there\nnew"), [0, 0, 10, 0, 2, 0, 0]);
+    }
+
+    #[test]
+    fn digits_in_any_script() {
+        assert_eq!(classes("٣٤"), [2, 0, 0, 0, 0, 0, 0]);
+    }
+}
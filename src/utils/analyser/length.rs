@@ -0,0 +1,22 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{models::properties::StringProperties, utils::analyser::Analyser};
+
+/// Grapheme cluster, code point and UTF-8 byte lengths.
+pub struct LengthAnalyser;
+
+impl Analyser for LengthAnalyser {
+    fn name(&self) -> &'static str {
+        "length"
+    }
+
+    fn version(&self) -> i32 {
+        1
+    }
+
+    fn analyse(&self, input: &str, properties: &mut StringProperties) {
+        properties.length = input.graphemes(true).count() as i32;
+        properties.byte_length = input.len() as i32;
+        properties.codepoint_length = input.chars().count() as i32;
+    }
+}
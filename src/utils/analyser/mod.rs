@@ -0,0 +1,34 @@
+pub mod characters;
+pub mod classes;
+pub mod length;
+pub mod palindrome;
+pub mod registry;
+pub mod vowels;
+pub mod words;
+
+use sha2::{Digest, Sha256};
+
+use crate::{models::properties::StringProperties, utils::analyser::registry::registry};
+
+/// A self-contained metric computed from a string's value.
+///
+/// Each analyser writes its output into `StringProperties` and is recorded under its
+/// `name` in `analyser_versions`, so stored rows can be compared with the running code.
+pub trait Analyser: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Bump whenever the output for an unchanged input may differ from earlier versions.
+    fn version(&self) -> i32;
+
+    fn analyse(&self, input: &str, properties: &mut StringProperties);
+}
+
+pub fn compute_sha256(input: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(input.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+pub fn analyse_string(input: &str) -> StringProperties {
+    registry().analyse(input)
+}
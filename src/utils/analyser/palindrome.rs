@@ -0,0 +1,23 @@
+use crate::{
+    models::{filters::PalindromeMode, properties::StringProperties},
+    utils::{analyser::Analyser, palindrome::is_palindrome},
+};
+
+/// Strict, loose and normalised palindrome checks.
+pub struct PalindromeAnalyser;
+
+impl Analyser for PalindromeAnalyser {
+    fn name(&self) -> &'static str {
+        "palindrome"
+    }
+
+    fn version(&self) -> i32 {
+        1
+    }
+
+    fn analyse(&self, input: &str, properties: &mut StringProperties) {
+        properties.is_palindrome = is_palindrome(input, PalindromeMode::Strict);
+        properties.is_loose_palindrome = is_palindrome(input, PalindromeMode::Loose);
+        properties.is_normalised_palindrome = is_palindrome(input, PalindromeMode::Normalised);
+    }
+}
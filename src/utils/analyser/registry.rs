@@ -0,0 +1,82 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use crate::{
+    models::properties::StringProperties,
+    utils::analyser::{
+        Analyser, characters::CharacterAnalyser, classes::CharacterClassAnalyser, compute_sha256,
+        length::LengthAnalyser, palindrome::PalindromeAnalyser, vowels::VowelAnalyser,
+        words::WordCountAnalyser,
+    },
+};
+
+static REGISTRY: LazyLock<AnalyserRegistry> = LazyLock::new(AnalyserRegistry::builtin);
+
+/// The registry `analyse_string` runs every string through.
+pub fn registry() -> &'static AnalyserRegistry {
+    &REGISTRY
+}
+
+pub struct AnalyserRegistry {
+    analysers: Vec<Box<dyn Analyser>>,
+}
+
+impl AnalyserRegistry {
+    pub fn empty() -> Self {
+        Self {
+            analysers: Vec::new(),
+        }
+    }
+
+    pub fn builtin() -> Self {
+        Self::empty()
+            .register(LengthAnalyser)
+            .register(PalindromeAnalyser)
+            .register(CharacterAnalyser)
+            .register(WordCountAnalyser)
+            .register(CharacterClassAnalyser)
+            .register(VowelAnalyser)
+    }
+
+    /// Adds an analyser, replacing any previously registered one with the same name.
+    pub fn register(mut self, analyser: impl Analyser + 'static) -> Self {
+        self.analysers
+            .retain(|registered| registered.name() != analyser.name());
+        self.analysers.push(Box::new(analyser));
+        self
+    }
+
+    pub fn analysers(&self) -> impl Iterator<Item = &dyn Analyser> {
+        self.analysers.iter().map(|analyser| analyser.as_ref())
+    }
+
+    pub fn analyse(&self, input: &str) -> StringProperties {
+        let mut properties = StringProperties {
+            sha256_hash: compute_sha256(input),
+            analyser_versions: self.versions(),
+            ..StringProperties::default()
+        };
+
+        for analyser in &self.analysers {
+            analyser.analyse(input, &mut properties);
+        }
+
+        properties
+    }
+
+    /// Current version of every registered analyser, keyed by name.
+    pub fn versions(&self) -> HashMap<String, i32> {
+        self.analysers
+            .iter()
+            .map(|analyser| (analyser.name().to_string(), analyser.version()))
+            .collect()
+    }
+
+    /// Names of the analysers whose stored version is missing or differs from the current one.
+    pub fn outdated<'a>(&'a self, stored_versions: &HashMap<String, i32>) -> Vec<&'a str> {
+        self.analysers
+            .iter()
+            .filter(|analyser| stored_versions.get(analyser.name()) != Some(&analyser.version()))
+            .map(|analyser| analyser.name())
+            .collect()
+    }
+}
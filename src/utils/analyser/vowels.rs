@@ -0,0 +1,49 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_properties::{GeneralCategoryGroup, UnicodeGeneralCategory};
+
+use crate::{models::properties::StringProperties, utils::analyser::Analyser};
+
+/// Vowel and consonant counts, the share of vowels among them and the longest run of
+/// consecutive consonants. Latin letters are read without their accents, so "é" is a vowel
+/// and "ñ" a consonant; "y" is a consonant.
+pub struct VowelAnalyser;
+
+impl Analyser for VowelAnalyser {
+    fn name(&self) -> &'static str {
+        "vowels"
+    }
+
+    fn version(&self) -> i32 {
+        1
+    }
+
+    fn analyse(&self, input: &str, properties: &mut StringProperties) {
+        let mut run = 0;
+
+        for c in input.nfd() {
+            // Accents decompose into combining marks, which belong to the letter before.
+            if c.general_category_group() == GeneralCategoryGroup::Mark {
+                continue;
+            }
+
+            match c.to_ascii_lowercase() {
+                'a' | 'e' | 'i' | 'o' | 'u' => {
+                    properties.vowel_count += 1;
+                    run = 0;
+                }
+                'a'..='z' => {
+                    properties.consonant_count += 1;
+                    run += 1;
+                    properties.longest_consonant_run = properties.longest_consonant_run.max(run);
+                }
+                _ => run = 0,
+            }
+        }
+
+        let letters = properties.vowel_count + properties.consonant_count;
+        properties.vowel_ratio = match letters {
+            0 => 0.0,
+            letters => properties.vowel_count as f64 / letters as f64,
+        };
+    }
+}
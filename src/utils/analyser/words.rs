@@ -0,0 +1,18 @@
+use crate::{models::properties::StringProperties, utils::analyser::Analyser};
+
+/// Number of whitespace-separated words.
+pub struct WordCountAnalyser;
+
+impl Analyser for WordCountAnalyser {
+    fn name(&self) -> &'static str {
+        "word_count"
+    }
+
+    fn version(&self) -> i32 {
+        1
+    }
+
+    fn analyse(&self, input: &str, properties: &mut StringProperties) {
+        properties.word_count = input.split_whitespace().count() as i32;
+    }
+}
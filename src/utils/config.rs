@@ -1,7 +1,9 @@
-use std::env;
+use std::{env, path::PathBuf};
 
 use anyhow::{Result, anyhow};
 
+use crate::utils::nlp::{DEFAULT_APPROXIMATE_TOLERANCE, NlpOptions, VocabularyStore};
+
 pub fn load_config() -> Result<(String, String, u32, u64, String, u32)> {
     let database_url =
         env::var("DATABASE_URL").map_err(|e| anyhow!("Missing DATABASE_URL: {}", e))?;
@@ -42,3 +44,62 @@ pub fn load_config() -> Result<(String, String, u32, u64, String, u32)> {
         port,
     ))
 }
+
+/// Token required by `/admin` endpoints. Admin endpoints are disabled when `ADMIN_TOKEN` is unset.
+pub fn load_admin_token() -> Option<String> {
+    env::var("ADMIN_TOKEN")
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// Key that signs pagination cursors. Without `CURSOR_SECRET` a random key is used, so cursors
+/// stop working on restart and are not accepted by other instances.
+pub fn load_cursor_secret() -> Result<Vec<u8>> {
+    if let Ok(secret) = env::var("CURSOR_SECRET")
+        && !secret.trim().is_empty()
+    {
+        return Ok(secret.into_bytes());
+    }
+
+    tracing::warn!("CURSOR_SECRET is unset; pagination cursors will not survive a restart");
+
+    let mut secret = vec![0; 32];
+    getrandom::getrandom(&mut secret)
+        .map_err(|e| anyhow!("Could not generate a cursor secret: {}", e))?;
+
+    Ok(secret)
+}
+
+/// Natural language parser settings. `NLP_APPROXIMATE_TOLERANCE` is the percentage "about N"
+/// may stray from N, 0 to 100.
+pub fn load_nlp_options() -> Result<NlpOptions> {
+    let approximate_tolerance = match env::var("NLP_APPROXIMATE_TOLERANCE") {
+        Ok(value) => value
+            .parse::<u32>()
+            .ok()
+            .filter(|tolerance| *tolerance <= 100)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Invalid NLP_APPROXIMATE_TOLERANCE '{}': expected a percentage from 0 to 100",
+                    value
+                )
+            })?,
+        Err(_) => DEFAULT_APPROXIMATE_TOLERANCE,
+    };
+
+    Ok(NlpOptions {
+        approximate_tolerance,
+        language: None,
+    })
+}
+
+/// Natural language vocabulary from the `.toml` or `.json` file at `NLP_VOCABULARY_PATH`, or
+/// the built-in one when unset.
+pub fn load_nlp_vocabulary() -> Result<VocabularyStore> {
+    let path = env::var("NLP_VOCABULARY_PATH")
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+        .map(PathBuf::from);
+
+    VocabularyStore::load(path).map_err(|e| anyhow!(e))
+}
@@ -0,0 +1,609 @@
+use crate::models::{
+    expression::{Comparison, Condition, ExpressionError, FilterExpression},
+    filters::{PalindromeMode, Timestamp, character_set},
+};
+
+pub const MAX_EXPRESSION_LENGTH: usize = 1024;
+/// Bounds parenthesis and `NOT` nesting so a hostile expression cannot exhaust the stack.
+pub const MAX_EXPRESSION_DEPTH: usize = 32;
+
+/// Parses a `filter` expression.
+///
+/// ```text
+/// expression := and_term ("OR" and_term)*
+/// and_term   := unary ("AND" unary)*
+/// unary      := "NOT" unary | "(" expression ")" | key ":" [">" | ">=" | "<" | "<="] value
+/// value      := bare word | "double quoted" (with \" and \\ escapes)
+/// ```
+///
+/// `AND`, `OR` and `NOT` are case-insensitive; `NOT` binds tightest, then `AND`, then `OR`.
+pub fn parse_filter_expression(input: &str) -> Result<FilterExpression, ExpressionError> {
+    if input.chars().count() > MAX_EXPRESSION_LENGTH {
+        return Err(ExpressionError::new(
+            format!(
+                "Expression is longer than {} characters",
+                MAX_EXPRESSION_LENGTH
+            ),
+            MAX_EXPRESSION_LENGTH,
+        ));
+    }
+
+    let tokens = tokenise(input)?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        depth: 0,
+        end: input.chars().count(),
+    };
+
+    let expression = parser.expression()?;
+
+    match parser.tokens.get(parser.index) {
+        None => Ok(expression),
+        Some((Token::RightParen, position)) => {
+            Err(ExpressionError::new("Unmatched ')'", *position))
+        }
+        Some((_, position)) => Err(ExpressionError::new("Expected AND or OR", *position)),
+    }
+}
+
+#[derive(Debug)]
+enum Token {
+    LeftParen,
+    RightParen,
+    And,
+    Or,
+    Not,
+    Term {
+        key: String,
+        comparison: Option<Comparison>,
+        value: String,
+        value_position: usize,
+    },
+}
+
+/// Splits the input into tokens, each paired with the character offset it starts at.
+fn tokenise(input: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+    let chars = input.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let start = index;
+
+        match chars[index] {
+            c if c.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '(' => {
+                tokens.push((Token::LeftParen, start));
+                index += 1;
+                continue;
+            }
+            ')' => {
+                tokens.push((Token::RightParen, start));
+                index += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        while index < chars.len() && !is_delimiter(chars[index]) && chars[index] != ':' {
+            index += 1;
+        }
+        let word = chars[start..index].iter().collect::<String>();
+
+        if chars.get(index) != Some(&':') {
+            let token = match word.to_uppercase().as_str() {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "NOT" => Token::Not,
+                _ => {
+                    return Err(ExpressionError::new(
+                        format!("Expected key:value, AND, OR or NOT, found '{}'", word),
+                        start,
+                    ));
+                }
+            };
+            tokens.push((token, start));
+            continue;
+        }
+
+        if word.is_empty() {
+            return Err(ExpressionError::new("Missing key before ':'", start));
+        }
+        index += 1;
+
+        let (comparison, comparison_len) = match (chars.get(index), chars.get(index + 1)) {
+            (Some('>'), Some('=')) => (Some(Comparison::GreaterOrEqual), 2),
+            (Some('<'), Some('=')) => (Some(Comparison::LessOrEqual), 2),
+            (Some('>'), _) => (Some(Comparison::Greater), 1),
+            (Some('<'), _) => (Some(Comparison::Less), 1),
+            _ => (None, 0),
+        };
+        index += comparison_len;
+
+        let value_position = index;
+        let value = if chars.get(index) == Some(&'"') {
+            index += 1;
+            let mut value = String::new();
+            loop {
+                match chars.get(index) {
+                    None => {
+                        return Err(ExpressionError::new(
+                            "Unterminated quoted value",
+                            value_position,
+                        ));
+                    }
+                    Some('"') => {
+                        index += 1;
+                        break;
+                    }
+                    Some('\\') if matches!(chars.get(index + 1), Some('"' | '\\')) => {
+                        value.push(chars[index + 1]);
+                        index += 2;
+                    }
+                    Some(c) => {
+                        value.push(*c);
+                        index += 1;
+                    }
+                }
+            }
+            value
+        } else {
+            while index < chars.len() && !is_delimiter(chars[index]) {
+                index += 1;
+            }
+            chars[value_position..index].iter().collect::<String>()
+        };
+
+        if value.is_empty() {
+            return Err(ExpressionError::new(
+                format!("Missing value for '{}'", word),
+                value_position,
+            ));
+        }
+
+        tokens.push((
+            Token::Term {
+                key: word,
+                comparison,
+                value,
+                value_position,
+            },
+            start,
+        ));
+    }
+
+    Ok(tokens)
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')'
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    depth: usize,
+    /// Character offset reported when the input ends too early.
+    end: usize,
+}
+
+impl Parser {
+    fn expression(&mut self) -> Result<FilterExpression, ExpressionError> {
+        let mut expression = self.and_term()?;
+
+        while let Some((Token::Or, _)) = self.tokens.get(self.index) {
+            self.index += 1;
+            expression = FilterExpression::Or(Box::new(expression), Box::new(self.and_term()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn and_term(&mut self) -> Result<FilterExpression, ExpressionError> {
+        let mut expression = self.unary()?;
+
+        while let Some((Token::And, _)) = self.tokens.get(self.index) {
+            self.index += 1;
+            expression = FilterExpression::And(Box::new(expression), Box::new(self.unary()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn unary(&mut self) -> Result<FilterExpression, ExpressionError> {
+        let Some((token, position)) = self.tokens.get(self.index) else {
+            return Err(ExpressionError::new(
+                "Unexpected end of expression",
+                self.end,
+            ));
+        };
+        let position = *position;
+
+        match token {
+            Token::Not => {
+                self.index += 1;
+                self.nested(position, |parser| parser.unary())
+                    .map(|expression| FilterExpression::Not(Box::new(expression)))
+            }
+            Token::LeftParen => {
+                self.index += 1;
+                let expression = self.nested(position, |parser| parser.expression())?;
+
+                match self.tokens.get(self.index) {
+                    Some((Token::RightParen, _)) => {
+                        self.index += 1;
+                        Ok(expression)
+                    }
+                    _ => Err(ExpressionError::new("Unmatched '('", position)),
+                }
+            }
+            Token::Term {
+                key,
+                comparison,
+                value,
+                value_position,
+            } => {
+                let condition = condition(key, *comparison, value, position, *value_position)?;
+                self.index += 1;
+                Ok(FilterExpression::Condition(condition))
+            }
+            Token::RightParen => Err(ExpressionError::new("Unexpected ')'", position)),
+            Token::And | Token::Or => Err(ExpressionError::new(
+                "Expected a condition before AND/OR",
+                position,
+            )),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        position: usize,
+        parse: impl FnOnce(&mut Self) -> Result<FilterExpression, ExpressionError>,
+    ) -> Result<FilterExpression, ExpressionError> {
+        if self.depth >= MAX_EXPRESSION_DEPTH {
+            return Err(ExpressionError::new(
+                format!("Expression nested deeper than {}", MAX_EXPRESSION_DEPTH),
+                position,
+            ));
+        }
+
+        self.depth += 1;
+        let expression = parse(self);
+        self.depth -= 1;
+
+        expression
+    }
+}
+
+/// Maps a `key:value` term onto a condition, checking the key, comparison and value type.
+fn condition(
+    key: &str,
+    comparison: Option<Comparison>,
+    value: &str,
+    key_position: usize,
+    value_position: usize,
+) -> Result<Condition, ExpressionError> {
+    let key = key.to_lowercase();
+
+    let palindrome_mode = match key.as_str() {
+        "is_palindrome" => Some(PalindromeMode::Strict),
+        "is_loose_palindrome" => Some(PalindromeMode::Loose),
+        "is_normalised_palindrome" | "is_normalized_palindrome" => Some(PalindromeMode::Normalised),
+        _ => None,
+    };
+
+    let numeric_column = match key.as_str() {
+        "length" => Some("length"),
+        "byte_length" => Some("byte_length"),
+        "codepoint_length" => Some("codepoint_length"),
+        "word_count" => Some("word_count"),
+        "unique_characters" => Some("unique_char_count"),
+        "digit_count" => Some("digit_count"),
+        "uppercase_count" => Some("uppercase_count"),
+        "lowercase_count" => Some("lowercase_count"),
+        "punctuation_count" => Some("punctuation_count"),
+        "whitespace_count" => Some("whitespace_count"),
+        "symbol_count" => Some("symbol_count"),
+        "emoji_count" => Some("emoji_count"),
+        "vowel_count" => Some("vowel_count"),
+        "consonant_count" => Some("consonant_count"),
+        "longest_consonant_run" => Some("longest_consonant_run"),
+        _ => None,
+    };
+
+    if let Some(column) = numeric_column {
+        let value = value.parse::<i32>().map_err(|_| {
+            ExpressionError::new(
+                format!("'{}' expects an integer, found '{}'", key, value),
+                value_position,
+            )
+        })?;
+
+        return Ok(Condition::Numeric {
+            column,
+            comparison: comparison.unwrap_or(Comparison::Equal),
+            value,
+        });
+    }
+
+    if key == "vowel_ratio" {
+        let value = value
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| {
+                ExpressionError::new(
+                    format!("'{}' expects a number, found '{}'", key, value),
+                    value_position,
+                )
+            })?;
+
+        return Ok(Condition::Ratio {
+            column: "vowel_ratio",
+            comparison: comparison.unwrap_or(Comparison::Equal),
+            value,
+        });
+    }
+
+    if key == "created_at" {
+        let comparison = comparison.ok_or_else(|| {
+            ExpressionError::new(
+                "'created_at' needs a comparison: >, >=, < or <=",
+                value_position,
+            )
+        })?;
+        let value = Timestamp::parse(value)
+            .map_err(|reason| ExpressionError::new(reason, value_position))?;
+
+        return Ok(Condition::CreatedAt { comparison, value });
+    }
+
+    if comparison.is_some() {
+        return Err(ExpressionError::new(
+            format!("'{}' does not support comparisons", key),
+            value_position,
+        ));
+    }
+
+    if let Some(mode) = palindrome_mode {
+        let value = value.to_lowercase().parse::<bool>().map_err(|_| {
+            ExpressionError::new(
+                format!("'{}' expects true or false, found '{}'", key, value),
+                value_position,
+            )
+        })?;
+
+        return Ok(Condition::Palindrome { mode, value });
+    }
+
+    match key.as_str() {
+        "contains" => Ok(Condition::Contains(character_set(Some(value)))),
+        "starts_with" => Ok(Condition::StartsWith(value.to_string())),
+        "ends_with" => Ok(Condition::EndsWith(value.to_string())),
+        "contains_substring" => Ok(Condition::ContainsSubstring(value.to_string())),
+        _ => Err(ExpressionError::new(
+            format!("Unknown filter key '{}'", key),
+            key_position,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_EXPRESSION_DEPTH, MAX_EXPRESSION_LENGTH, parse_filter_expression};
+    use crate::models::expression::{Condition, FilterExpression};
+
+    fn parse(input: &str) -> FilterExpression {
+        parse_filter_expression(input).unwrap()
+    }
+
+    fn error(input: &str) -> (String, usize) {
+        let error = parse_filter_expression(input).unwrap_err();
+        (error.reason, error.position)
+    }
+
+    fn value_of(input: &str) -> Condition {
+        match parse(input) {
+            FilterExpression::Condition(condition) => condition,
+            expression => panic!("expected a condition, got {:?}", expression),
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            parse("length:1 OR length:2 AND length:3"),
+            parse("length:1 OR (length:2 AND length:3)")
+        );
+        assert_eq!(
+            parse("length:1 AND length:2 OR length:3"),
+            parse("(length:1 AND length:2) OR length:3")
+        );
+        assert_eq!(
+            parse("NOT length:1 AND length:2"),
+            parse("(NOT length:1) AND length:2")
+        );
+        assert_eq!(
+            parse("NOT length:1 OR length:2"),
+            parse("(NOT length:1) OR length:2")
+        );
+        assert_eq!(
+            parse("length:1 OR length:2 OR length:3"),
+            parse("(length:1 OR length:2) OR length:3")
+        );
+        assert_eq!(
+            parse("length:1 and not length:2 or length:3"),
+            parse("length:1 AND NOT length:2 OR length:3")
+        );
+        assert_ne!(
+            parse("length:1 AND (length:2 OR length:3)"),
+            parse("length:1 AND length:2 OR length:3")
+        );
+    }
+
+    #[test]
+    fn quoting_and_escapes() {
+        assert_eq!(
+            value_of(r#"starts_with:"hello world""#),
+            Condition::StartsWith("hello world".to_string())
+        );
+        assert_eq!(
+            value_of(r#"ends_with:"say \"hi\"""#),
+            Condition::EndsWith(r#"say "hi""#.to_string())
+        );
+        assert_eq!(
+            value_of(r#"contains_substring:"a\\b""#),
+            Condition::ContainsSubstring(r"a\b".to_string())
+        );
+        assert_eq!(
+            value_of(r#"contains_substring:"a\nb""#),
+            Condition::ContainsSubstring(r"a\nb".to_string())
+        );
+        assert_eq!(
+            value_of(r#"starts_with:"(AND) OR""#),
+            Condition::StartsWith("(AND) OR".to_string())
+        );
+        assert_eq!(
+            value_of(r#"starts_with:">=5""#),
+            Condition::StartsWith(">=5".to_string())
+        );
+        assert_eq!(
+            error(r#"starts_with:"open"#),
+            ("Unterminated quoted value".to_string(), 12)
+        );
+        assert_eq!(
+            error(r#"starts_with:"""#),
+            ("Missing value for 'starts_with'".to_string(), 12)
+        );
+    }
+
+    #[test]
+    fn depth_limit() {
+        let nested = |depth: usize| format!("{}length:1{}", "(".repeat(depth), ")".repeat(depth));
+        let too_deep = format!("Expression nested deeper than {}", MAX_EXPRESSION_DEPTH);
+
+        assert!(parse_filter_expression(&nested(MAX_EXPRESSION_DEPTH)).is_ok());
+        assert_eq!(
+            error(&nested(MAX_EXPRESSION_DEPTH + 1)),
+            (too_deep.clone(), MAX_EXPRESSION_DEPTH)
+        );
+
+        let negated = |depth: usize| format!("{}length:1", "NOT ".repeat(depth));
+        assert!(parse_filter_expression(&negated(MAX_EXPRESSION_DEPTH)).is_ok());
+        assert_eq!(
+            error(&negated(MAX_EXPRESSION_DEPTH + 1)),
+            (too_deep, MAX_EXPRESSION_DEPTH * 4)
+        );
+    }
+
+    #[test]
+    fn error_positions() {
+        let cases = [
+            ("length:1 AND", "Unexpected end of expression", 12),
+            ("length:1 length:2", "Expected AND or OR", 9),
+            ("(length:1", "Unmatched '('", 0),
+            ("length:1)", "Unmatched ')'", 8),
+            (")", "Unexpected ')'", 0),
+            ("AND length:1", "Expected a condition before AND/OR", 0),
+            ("foo", "Expected key:value, AND, OR or NOT, found 'foo'", 0),
+            (":x", "Missing key before ':'", 0),
+            ("length:", "Missing value for 'length'", 7),
+            ("colour:red", "Unknown filter key 'colour'", 0),
+            ("length:abc", "'length' expects an integer, found 'abc'", 7),
+            (
+                "word_count:1 AND is_palindrome:maybe",
+                "'is_palindrome' expects true or false, found 'maybe'",
+                31,
+            ),
+            (
+                "starts_with:>a",
+                "'starts_with' does not support comparisons",
+                13,
+            ),
+            (
+                "created_at:2026-01-01",
+                "'created_at' needs a comparison: >, >=, < or <=",
+                11,
+            ),
+            // Positions count characters, not bytes.
+            (
+                "contains:é OR NOT lenght:3",
+                "Unknown filter key 'lenght'",
+                18,
+            ),
+        ];
+
+        for (input, reason, position) in cases {
+            assert_eq!(error(input), (reason.to_string(), position), "{}", input);
+        }
+
+        let long = "a".repeat(MAX_EXPRESSION_LENGTH + 1);
+        assert_eq!(
+            error(&long),
+            (
+                format!(
+                    "Expression is longer than {} characters",
+                    MAX_EXPRESSION_LENGTH
+                ),
+                MAX_EXPRESSION_LENGTH
+            )
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let inputs = [
+            "is_palindrome:true",
+            "is_loose_palindrome:false AND is_normalised_palindrome:true",
+            "length:>5 AND word_count:<=2",
+            "unique_characters:>=3 OR byte_length:<10",
+            "vowel_ratio:>=0.5 AND consonant_count:0",
+            "created_at:>=2026-01-01 AND created_at:<2026-02-01T12:30:00Z",
+            "(length:1 OR length:2) AND NOT (word_count:1 AND word_count:2)",
+            "length:1 AND (length:2 AND length:3)",
+            "length:1 OR (length:2 OR length:3)",
+            "NOT NOT is_palindrome:true",
+            "NOT (length:1 OR length:2) OR length:3",
+            "contains:ABC AND NOT contains:z",
+            r#"starts_with:"hello world" OR ends_with:"say \"hi\"""#,
+            r#"contains_substring:"a\\b" AND starts_with:">x" AND ends_with:"(y)""#,
+        ];
+
+        for input in inputs {
+            let expression = parse(input);
+            let rendered = expression.to_string();
+
+            assert_eq!(
+                parse(&rendered),
+                expression,
+                "{} rendered as {}",
+                input,
+                rendered
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            parse("(length:1 OR length:2) AND NOT (word_count:1 AND word_count:2)").to_string(),
+            "(length:1 OR length:2) AND NOT (word_count:1 AND word_count:2)"
+        );
+        assert_eq!(
+            parse("length:1 AND (length:2 AND length:3)").to_string(),
+            "length:1 AND (length:2 AND length:3)"
+        );
+        assert_eq!(
+            parse("(length:1 AND length:2) AND length:3").to_string(),
+            "length:1 AND length:2 AND length:3"
+        );
+        assert_eq!(
+            parse(r#"starts_with:"a b" AND unique_characters:>=3"#).to_string(),
+            r#"starts_with:"a b" AND unique_characters:>=3"#
+        );
+    }
+}
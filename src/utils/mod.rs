@@ -1,3 +1,6 @@
 pub mod analyser;
 pub mod config;
+pub mod expression;
 pub mod nlp;
+pub mod palindrome;
+pub mod similarity;
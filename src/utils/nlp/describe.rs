@@ -0,0 +1,1060 @@
+use crate::models::{
+    expression::{Comparison, Condition, FilterExpression},
+    filters::{
+        CharacterClass, LengthUnit, PalindromeMode, SearchLanguage, SortField, SortOrder,
+        StringFilters, Timestamp, character_set,
+    },
+};
+
+/// Describes filters as an English sentence, e.g. "palindromes longer than 5 characters
+/// containing the letter a".
+///
+/// Everything the natural language grammar can express is phrased so that
+/// `parse_natural_language` reads the sentence back into the same filters. Filters it
+/// cannot express ("starting with", regular expressions, search) are still described, but
+/// do not round-trip.
+pub fn describe_filters(filters: &StringFilters) -> String {
+    let mut sentence = Sentence {
+        count: filters.max_results,
+        ..Default::default()
+    };
+
+    let bounded_length = filters.numeric_ranges().iter().any(|range| {
+        range.name == "length"
+            && (range.exact.is_some() || range.min.is_some() || range.max.is_some())
+    });
+    // "longest" measures characters; a sort by bytes is "with the most bytes" instead, unless
+    // a length filter already names the unit.
+    let unit_sort = filters.sort == Some(SortField::Length) && !bounded_length;
+    let length_unit = filters.length_unit.filter(|_| unit_sort);
+
+    let mut sorted_by = None;
+    if let Some(sort) = filters.sort {
+        let most = match filters.sort_order() {
+            SortOrder::Desc => "most",
+            SortOrder::Asc => "fewest",
+        };
+        match (sort, filters.sort_order()) {
+            (SortField::Length, _) if let Some(unit) = length_unit => {
+                sentence.ranked = Some(format!("with the {} {}", most, unit_words(unit.column()).1))
+            }
+            (SortField::Length, SortOrder::Desc) => sentence.sort = Some("longest"),
+            (SortField::Length, SortOrder::Asc) => sentence.sort = Some("shortest"),
+            (SortField::CreatedAt, SortOrder::Desc) => sentence.sort = Some("newest"),
+            (SortField::CreatedAt, SortOrder::Asc) => sentence.sort = Some("oldest"),
+            (SortField::Value, SortOrder::Asc) => sentence.sort = Some("alphabetical"),
+            (SortField::WordCount, _) => sentence.ranked = Some(format!("with the {} words", most)),
+            (SortField::UniqueCharacters, _) => {
+                sentence.ranked = Some(format!("with the {} unique characters", most))
+            }
+            (sort, order) => sorted_by = Some((sort, order)),
+        }
+    }
+
+    if let Some(value) = filters.is_palindrome {
+        sentence.noun = Some(palindromes(filters.palindrome_mode, value));
+    }
+
+    for range in filters.numeric_ranges() {
+        if let Some(value) = range.exact {
+            sentence
+                .qualifiers
+                .push(numeric(range.column, Comparison::Equal, value));
+        }
+
+        match (range.min, range.max) {
+            (Some(min), Some(max)) => sentence.qualifiers.push(bounded(range.column, min, max)),
+            (Some(min), None) => {
+                sentence
+                    .qualifiers
+                    .push(numeric(range.column, Comparison::GreaterOrEqual, min))
+            }
+            (None, Some(max)) => {
+                sentence
+                    .qualifiers
+                    .push(numeric(range.column, Comparison::LessOrEqual, max))
+            }
+            (None, None) => {}
+        }
+    }
+
+    match (filters.min_vowel_ratio, filters.max_vowel_ratio) {
+        (Some(min), Some(max)) => sentence
+            .qualifiers
+            .push(format!("whose vowel ratio is from {} to {}", min, max)),
+        (Some(min), None) => {
+            sentence
+                .qualifiers
+                .push(ratio("vowel_ratio", Comparison::GreaterOrEqual, min))
+        }
+        (None, Some(max)) => {
+            sentence
+                .qualifiers
+                .push(ratio("vowel_ratio", Comparison::LessOrEqual, max))
+        }
+        (None, None) => {}
+    }
+
+    if !bounded_length
+        && length_unit.is_none()
+        && let Some(unit) = filters.length_unit
+    {
+        sentence
+            .qualifiers
+            .push(format!("measured in {}", unit_words(unit.column()).1));
+    }
+
+    if let Some(character) = &filters.contains_character {
+        sentence
+            .qualifiers
+            .push(contains(&character_set(Some(character))));
+    }
+
+    let all = character_set(filters.contains_all.as_deref());
+    if !all.is_empty() {
+        sentence
+            .qualifiers
+            .push(format!("containing {}", list(&all, "and")));
+    }
+
+    let any = character_set(filters.contains_any.as_deref());
+    if !any.is_empty() {
+        sentence
+            .qualifiers
+            .push(format!("containing {}", list(&any, "or")));
+    }
+
+    let excludes = character_set(filters.excludes.as_deref());
+    if !excludes.is_empty() {
+        sentence
+            .qualifiers
+            .push(format!("without {}", list(&excludes, "or")));
+    }
+
+    sentence.qualifiers.extend(character_counts(filters));
+    sentence
+        .qualifiers
+        .extend(created(filters.created_after, filters.created_before));
+
+    let text_filters = [
+        (&filters.starts_with, "starting with"),
+        (&filters.ends_with, "ending with"),
+        (&filters.contains_substring, "containing the substring"),
+        (&filters.regex, "matching the regular expression"),
+    ];
+    let mut text_filtered = false;
+    for (value, phrase) in text_filters {
+        if let Some(value) = value {
+            sentence
+                .qualifiers
+                .push(format!("{} {}", phrase, quote(value)));
+            text_filtered = true;
+        }
+    }
+    if text_filtered && filters.case_insensitive == Some(true) {
+        sentence.qualifiers.push("ignoring case".to_string());
+    }
+
+    if let Some(q) = &filters.q {
+        let language = match filters.language {
+            None => "",
+            Some(SearchLanguage::English) => "English ",
+            Some(SearchLanguage::French) => "French ",
+            Some(SearchLanguage::Spanish) => "Spanish ",
+            Some(SearchLanguage::Simple) => "unstemmed ",
+        };
+        sentence
+            .qualifiers
+            .push(format!("matching the {}search {}", language, quote(q)));
+    }
+
+    let mut description = match &filters.expression {
+        Some(expression @ FilterExpression::Or(..)) if sentence.is_plain() => {
+            let mut clauses = disjuncts(expression)
+                .into_iter()
+                .map(|disjunct| {
+                    let mut clause = Sentence::default();
+                    clause.add(disjunct);
+                    clause
+                })
+                .collect::<Vec<Sentence>>();
+            clauses[0].count = sentence.count;
+            clauses[0].sort = sentence.sort;
+            clauses[0].ranked = sentence.ranked.take();
+
+            clauses
+                .iter()
+                .map(Sentence::render)
+                .collect::<Vec<String>>()
+                .join(" or ")
+        }
+        Some(expression) => {
+            sentence.add(expression);
+            sentence.render()
+        }
+        None => sentence.render(),
+    };
+
+    if let Some((sort, order)) = sorted_by {
+        let field = match sort {
+            SortField::CreatedAt => "creation time",
+            SortField::Length => "length",
+            SortField::WordCount => "word count",
+            SortField::UniqueCharacters => "unique character count",
+            SortField::Value => "value",
+            SortField::Relevance => "relevance",
+        };
+        let order = match order {
+            SortOrder::Asc => "ascending",
+            SortOrder::Desc => "descending",
+        };
+        description.push_str(&format!(", sorted by {} in {} order", field, order));
+    }
+
+    description
+}
+
+/// "[the count] [sort] noun [ranked] qualifier qualifier ...", e.g. "the 5 longest
+/// palindromes with 1 word".
+#[derive(Default)]
+struct Sentence {
+    /// How many results are returned: "the 5 longest", "top 5".
+    count: Option<i64>,
+    sort: Option<&'static str>,
+    /// "palindromes" or "non-palindromes"; "strings" when unset.
+    noun: Option<String>,
+    /// A sort phrased after the noun: "with the most words".
+    ranked: Option<String>,
+    qualifiers: Vec<String>,
+}
+
+impl Sentence {
+    /// Whether nothing but a sort has been added, so "or" clauses can stand on their own.
+    fn is_plain(&self) -> bool {
+        self.noun.is_none() && self.qualifiers.is_empty()
+    }
+
+    /// Adds an expression as ANDed qualifiers, taking a palindrome check as the noun.
+    fn add(&mut self, expression: &FilterExpression) {
+        match expression {
+            expression if let Some(balance) = vowel_balance(expression) => {
+                self.qualifiers.push(format!("with {}", balance))
+            }
+            FilterExpression::And(..) if let Some(range) = created_range(expression) => {
+                self.qualifiers.push(range)
+            }
+            FilterExpression::And(..) => {
+                // Bounds on one column read as a single range: "exactly 5 characters long",
+                // "with between 2 and 3 words".
+                let terms = conjuncts(expression);
+                let mut paired = vec![false; terms.len()];
+                for (index, term) in terms.iter().enumerate() {
+                    if paired[index] {
+                        continue;
+                    }
+
+                    let partner = (index + 1..terms.len()).find_map(|other| {
+                        if paired[other] {
+                            return None;
+                        }
+                        match (bound_of(term), bound_of(terms[other])) {
+                            (
+                                Some((column, Bound::Min, min)),
+                                Some((other_column, Bound::Max, max)),
+                            )
+                            | (
+                                Some((column, Bound::Max, max)),
+                                Some((other_column, Bound::Min, min)),
+                            ) if column == other_column => Some((other, column, min, max)),
+                            _ => None,
+                        }
+                    });
+
+                    match partner {
+                        Some((other, column, min, max)) => {
+                            paired[other] = true;
+                            self.qualifiers.push(bounded(column, min, max));
+                        }
+                        None => self.add(term),
+                    }
+                }
+            }
+            FilterExpression::Condition(Condition::Palindrome { mode, value })
+                if self.noun.is_none() =>
+            {
+                self.noun = Some(palindromes(non_default(*mode), *value));
+            }
+            FilterExpression::Or(..) => {
+                if let Some(any) = contains_any(expression) {
+                    self.qualifiers
+                        .push(format!("containing {}", list(&any, "or")));
+                    return;
+                }
+
+                self.qualifiers
+                    .push(format!("that are either {}", clauses(expression, "or")));
+            }
+            FilterExpression::Not(inner) => self.qualifiers.push(negated(inner)),
+            FilterExpression::Condition(condition) => {
+                self.qualifiers.push(condition_phrase(condition))
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let ranked = self.count.is_some() || self.sort.is_some() || self.ranked.is_some();
+        if !ranked && self.is_plain() {
+            return "all strings".to_string();
+        }
+
+        let noun = self.noun.as_deref().unwrap_or("strings");
+        // "the shortest string" reads back as one result; "top 1 string" needs the number.
+        let noun = match self.count {
+            Some(1) => noun.strip_suffix('s').unwrap_or(noun),
+            _ => noun,
+        };
+
+        let mut words = Vec::new();
+        match (self.count, self.sort) {
+            (Some(1), Some(sort)) => words.extend(["the".to_string(), sort.to_string()]),
+            (Some(count), Some(sort)) => {
+                words.extend(["the".to_string(), count.to_string(), sort.to_string()])
+            }
+            (Some(count), None) => words.extend(["top".to_string(), count.to_string()]),
+            (None, sort) => words.extend(sort.map(str::to_string)),
+        }
+        words.push(noun.to_string());
+        words.extend(self.ranked.clone());
+        words.extend(self.qualifiers.iter().cloned());
+
+        words.join(" ")
+    }
+}
+
+/// Each OR-ed term as its own phrase: "palindromes or strings with 1 word".
+fn clauses(expression: &FilterExpression, conjunction: &str) -> String {
+    disjuncts(expression)
+        .into_iter()
+        .map(compound)
+        .collect::<Vec<String>>()
+        .join(&format!(" {} ", conjunction))
+}
+
+fn disjuncts(expression: &FilterExpression) -> Vec<&FilterExpression> {
+    match expression {
+        FilterExpression::Or(left, right) => {
+            let mut disjuncts = disjuncts(left);
+            disjuncts.extend(self::disjuncts(right));
+            disjuncts
+        }
+        expression => vec![expression],
+    }
+}
+
+/// Each AND-ed term, keeping together the pairs that read as one phrase ("more vowels than
+/// consonants", "created since X and before Y").
+fn conjuncts(expression: &FilterExpression) -> Vec<&FilterExpression> {
+    match expression {
+        FilterExpression::And(left, right)
+            if vowel_balance(expression).is_none() && created_range(expression).is_none() =>
+        {
+            let mut conjuncts = conjuncts(left);
+            conjuncts.extend(self::conjuncts(right));
+            conjuncts
+        }
+        expression => vec![expression],
+    }
+}
+
+enum Bound {
+    Min,
+    Max,
+}
+
+/// The inclusive bound a numeric term puts on its column.
+fn bound_of(expression: &FilterExpression) -> Option<(&'static str, Bound, i32)> {
+    let FilterExpression::Condition(Condition::Numeric {
+        column,
+        comparison,
+        value,
+    }) = expression
+    else {
+        return None;
+    };
+
+    match comparison {
+        Comparison::GreaterOrEqual => Some((column, Bound::Min, *value)),
+        Comparison::Greater => Some((column, Bound::Min, value.checked_add(1)?)),
+        Comparison::LessOrEqual => Some((column, Bound::Max, *value)),
+        Comparison::Less => Some((column, Bound::Max, value.checked_sub(1)?)),
+        Comparison::Equal => None,
+    }
+}
+
+/// The characters of an OR of single-character `contains` terms, as `contains_any` is
+/// written in an expression.
+fn contains_any(expression: &FilterExpression) -> Option<Vec<String>> {
+    disjuncts(expression)
+        .into_iter()
+        .map(|disjunct| match disjunct {
+            FilterExpression::Condition(Condition::Contains(characters))
+                if characters.len() == 1 =>
+            {
+                Some(characters[0].clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn condition_phrase(condition: &Condition) -> String {
+    match condition {
+        Condition::Palindrome { mode, value } => {
+            format!("that are {}", palindromes(non_default(*mode), *value))
+        }
+        Condition::Numeric {
+            column,
+            comparison,
+            value,
+        } => numeric(column, *comparison, *value),
+        Condition::Ratio {
+            column,
+            comparison,
+            value,
+        } => ratio(column, *comparison, *value),
+        Condition::CreatedAt {
+            comparison: Comparison::GreaterOrEqual,
+            value,
+        } => format!("created since {}", date(*value)),
+        Condition::CreatedAt {
+            comparison: Comparison::Greater,
+            value,
+        } => format!("created after {}", date(*value)),
+        Condition::CreatedAt {
+            comparison: Comparison::Less,
+            value,
+        } => format!("created before {}", date(*value)),
+        Condition::CreatedAt { value, .. } => format!("created at or before {}", date(*value)),
+        Condition::Contains(characters) => contains(characters),
+        Condition::StartsWith(value) => format!("starting with {}", quote(value)),
+        Condition::EndsWith(value) => format!("ending with {}", quote(value)),
+        Condition::ContainsSubstring(value) => {
+            format!("containing the substring {}", quote(value))
+        }
+    }
+}
+
+/// Phrases a negated term the way the grammar reads negations: "not exactly 3 characters",
+/// "not between 2 and 10 characters", "without z".
+fn negated(inner: &FilterExpression) -> String {
+    match inner {
+        inner if let Some(balance) = vowel_balance(inner) => format!("not with {}", balance),
+        FilterExpression::Condition(Condition::Numeric {
+            column,
+            comparison: Comparison::Equal,
+            value,
+        }) => not(column, &exact_words(column, *value)),
+        FilterExpression::Condition(Condition::Numeric {
+            column,
+            comparison,
+            value,
+        }) => numeric(column, complement(*comparison), *value),
+        FilterExpression::Condition(Condition::Ratio {
+            column,
+            comparison: Comparison::Equal,
+            value,
+        }) => format!("whose {} is not {}", count_name(column), value),
+        FilterExpression::Condition(Condition::Ratio {
+            column,
+            comparison,
+            value,
+        }) => ratio(column, complement(*comparison), *value),
+        FilterExpression::And(..) if let Some(range) = created_range(inner) => {
+            format!("not {}", range)
+        }
+        FilterExpression::And(left, right) => match (&**left, &**right) {
+            (
+                FilterExpression::Condition(Condition::Numeric {
+                    column,
+                    comparison: Comparison::GreaterOrEqual,
+                    value: min,
+                }),
+                FilterExpression::Condition(Condition::Numeric {
+                    column: max_column,
+                    comparison: Comparison::LessOrEqual,
+                    value: max,
+                }),
+            ) if column == max_column => not(column, &between_words(column, *min, *max)),
+            (
+                FilterExpression::Condition(Condition::Numeric {
+                    column,
+                    comparison: Comparison::GreaterOrEqual,
+                    value: 1,
+                }),
+                FilterExpression::Condition(Condition::Numeric {
+                    column: other,
+                    comparison: Comparison::LessOrEqual,
+                    value: 0,
+                }),
+            ) if let Some(case) = letter_case(column, other) => format!("that are not {}", case),
+            _ => format!("that are not {}", compound(inner)),
+        },
+        FilterExpression::Condition(Condition::Contains(characters)) if characters.len() == 1 => {
+            format!("without {}", character(&characters[0]))
+        }
+        FilterExpression::Condition(Condition::Contains(characters)) => {
+            format!("not containing all of {}", list(characters, "and"))
+        }
+        FilterExpression::Condition(Condition::Palindrome { mode, value }) => {
+            format!("that are {}", palindromes(non_default(*mode), !value))
+        }
+        FilterExpression::Condition(condition) => format!("not {}", condition_phrase(condition)),
+        FilterExpression::Not(inner) => compound(inner),
+        FilterExpression::Or(..) => format!("that are neither {}", clauses(inner, "nor")),
+    }
+}
+
+/// An expression as a standalone phrase, for nesting inside "either" or "not".
+fn compound(expression: &FilterExpression) -> String {
+    let mut sentence = Sentence::default();
+    sentence.add(expression);
+    sentence.render()
+}
+
+/// "that are not ... long" around a length or word count and "not with ..." around a
+/// character class, which the grammar reads as a negation; "whose ... is not ..." for other
+/// counts.
+fn not(column: &str, phrase: &str) -> String {
+    if is_length(column) || column == "word_count" {
+        format!("that are not {} long", phrase)
+    } else if is_class(column) {
+        format!("not with {}", phrase)
+    } else {
+        format!("whose {} is not {}", count_name(column), phrase)
+    }
+}
+
+/// Phrases `column comparison value`: "longer than 5 characters", "with 3 words",
+/// "containing digits", "whose unique character count is above 4".
+fn numeric(column: &str, comparison: Comparison, value: i32) -> String {
+    // Bounds read as "more than" and "fewer than" where they can: "at least 6" is "more
+    // than 5"; "at most" stays when the bound is already the largest number.
+    let (comparison, value) = match comparison {
+        Comparison::GreaterOrEqual if value > 0 => (Comparison::Greater, value - 1),
+        Comparison::LessOrEqual if value < i32::MAX => (Comparison::Less, value + 1),
+        comparison => (comparison, value),
+    };
+
+    if is_class(column) {
+        let (singular, plural) = unit_words(column);
+        let unit = if value == 1 { singular } else { plural };
+
+        return match comparison {
+            Comparison::Greater if value == 0 => format!("containing {}", plural),
+            Comparison::Less if value == 1 => format!("without {}", plural),
+            Comparison::Equal => format!("with {} {}", value, unit),
+            Comparison::Greater => format!("with more than {} {}", value, unit),
+            Comparison::Less => format!("with fewer than {} {}", value, unit),
+            Comparison::LessOrEqual => format!("with at most {} {}", value, unit),
+            Comparison::GreaterOrEqual => format!("with at least {} {}", value, unit),
+        };
+    }
+
+    if !is_length(column) && column != "word_count" {
+        return format!(
+            "whose {} is {}{}",
+            count_name(column),
+            bound(comparison),
+            value
+        );
+    }
+
+    let (singular, plural) = unit_words(column);
+    let unit = if value == 1 { singular } else { plural };
+
+    let phrase = match (comparison, column) {
+        (Comparison::Equal, "word_count") => return format!("with {} {}", value, unit),
+        (Comparison::Equal, _) => format!("exactly {} {} long", value, unit),
+        (Comparison::Greater, "word_count") => format!("more than {} {}", value, unit),
+        (Comparison::Greater, _) => format!("longer than {} {}", value, unit),
+        (Comparison::Less, "word_count") => format!("fewer than {} {}", value, unit),
+        (Comparison::Less, _) => format!("shorter than {} {}", value, unit),
+        (Comparison::LessOrEqual, "word_count") => format!("at most {} {}", value, unit),
+        (Comparison::LessOrEqual, _) => format!("at most {} {} long", value, unit),
+        (_, "word_count") => format!("at least {} {}", value, unit),
+        (_, _) => format!("at least {} {} long", value, unit),
+    };
+
+    if column == "word_count" {
+        format!("with {}", phrase)
+    } else {
+        phrase
+    }
+}
+
+/// "whose vowel ratio is at least 0.4".
+fn ratio(column: &str, comparison: Comparison, value: f64) -> String {
+    format!(
+        "whose {} is {}{}",
+        count_name(column),
+        bound(comparison),
+        value
+    )
+}
+
+fn bound(comparison: Comparison) -> &'static str {
+    match comparison {
+        Comparison::Equal => "",
+        Comparison::Greater => "above ",
+        Comparison::GreaterOrEqual => "at least ",
+        Comparison::Less => "below ",
+        Comparison::LessOrEqual => "at most ",
+    }
+}
+
+/// "more vowels than consonants" for the expressions the grammar reads it into: a vowel
+/// ratio above a half, or below a half with at least one consonant.
+fn vowel_balance(expression: &FilterExpression) -> Option<&'static str> {
+    match expression {
+        FilterExpression::Condition(Condition::Ratio {
+            column: "vowel_ratio",
+            comparison: Comparison::Greater,
+            value,
+        }) if *value == 0.5 => Some("more vowels than consonants"),
+        FilterExpression::And(left, right) => match (&**left, &**right) {
+            (
+                FilterExpression::Condition(Condition::Ratio {
+                    column: "vowel_ratio",
+                    comparison: Comparison::Less,
+                    value,
+                }),
+                FilterExpression::Condition(Condition::Numeric {
+                    column: "consonant_count",
+                    comparison: Comparison::GreaterOrEqual,
+                    value: 1,
+                }),
+            ) if *value == 0.5 => Some("more consonants than vowels"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `min..=max` on a column: an exact count when both ends meet, otherwise a range.
+fn bounded(column: &str, min: i32, max: i32) -> String {
+    if min == max {
+        numeric(column, Comparison::Equal, min)
+    } else {
+        between(column, min, max)
+    }
+}
+
+fn between(column: &str, min: i32, max: i32) -> String {
+    match column {
+        column if column == "word_count" || is_class(column) => {
+            format!("with {}", between_words(column, min, max))
+        }
+        column if is_length(column) => format!("{} long", between_words(column, min, max)),
+        column => format!(
+            "whose {} is {}",
+            count_name(column),
+            between_words(column, min, max)
+        ),
+    }
+}
+
+fn between_words(column: &str, min: i32, max: i32) -> String {
+    if is_length(column) || column == "word_count" || is_class(column) {
+        format!("between {} and {} {}", min, max, unit_words(column).1)
+    } else {
+        format!("from {} to {}", min, max)
+    }
+}
+
+/// "3 words", "3 digits" or "exactly 3 characters", for a negated exact count.
+fn exact_words(column: &str, value: i32) -> String {
+    let (singular, plural) = unit_words(column);
+    let unit = if value == 1 { singular } else { plural };
+
+    match column {
+        column if column == "word_count" || is_class(column) => format!("{} {}", value, unit),
+        column if is_length(column) => format!("exactly {} {}", value, unit),
+        _ => value.to_string(),
+    }
+}
+
+fn complement(comparison: Comparison) -> Comparison {
+    match comparison {
+        Comparison::Equal => Comparison::Equal,
+        Comparison::Greater => Comparison::LessOrEqual,
+        Comparison::GreaterOrEqual => Comparison::Less,
+        Comparison::Less => Comparison::GreaterOrEqual,
+        Comparison::LessOrEqual => Comparison::Greater,
+    }
+}
+
+fn is_length(column: &str) -> bool {
+    [
+        LengthUnit::Graphemes,
+        LengthUnit::Bytes,
+        LengthUnit::Codepoints,
+    ]
+    .iter()
+    .any(|unit| unit.column() == column)
+}
+
+fn is_class(column: &str) -> bool {
+    CharacterClass::ALL
+        .iter()
+        .any(|class| class.column() == column)
+}
+
+/// "all-caps" for at least one uppercase and no lowercase letter, as the grammar reads it.
+fn letter_case(cased: &str, other: &str) -> Option<&'static str> {
+    match (cased, other) {
+        ("uppercase_count", "lowercase_count") => Some("all-caps"),
+        ("lowercase_count", "uppercase_count") => Some("all-lowercase"),
+        _ => None,
+    }
+}
+
+fn unit_words(column: &str) -> (&'static str, &'static str) {
+    match column {
+        "byte_length" => ("byte", "bytes"),
+        "codepoint_length" => ("code point", "code points"),
+        "word_count" => ("word", "words"),
+        "digit_count" => ("digit", "digits"),
+        "uppercase_count" => ("uppercase letter", "uppercase letters"),
+        "lowercase_count" => ("lowercase letter", "lowercase letters"),
+        "punctuation_count" => ("punctuation mark", "punctuation marks"),
+        "whitespace_count" => ("whitespace character", "whitespace characters"),
+        "symbol_count" => ("symbol", "symbols"),
+        "emoji_count" => ("emoji", "emoji"),
+        "vowel_count" => ("vowel", "vowels"),
+        "consonant_count" => ("consonant", "consonants"),
+        _ => ("character", "characters"),
+    }
+}
+
+fn count_name(column: &str) -> String {
+    match column {
+        "unique_char_count" => "unique character count".to_string(),
+        column => column.replace('_', " "),
+    }
+}
+
+/// "created since 2026-01-01 and before 2026-02-01", read back as a single range so a
+/// negation covers both ends.
+fn created(after: Option<Timestamp>, before: Option<Timestamp>) -> Option<String> {
+    match (after, before) {
+        (Some(after), Some(before)) => Some(format!(
+            "created since {} and before {}",
+            date(after),
+            date(before)
+        )),
+        (Some(after), None) => Some(format!("created since {}", date(after))),
+        (None, Some(before)) => Some(format!("created before {}", date(before))),
+        (None, None) => None,
+    }
+}
+
+/// The range an expression's "since X and before Y" was parsed into.
+fn created_range(expression: &FilterExpression) -> Option<String> {
+    match expression {
+        FilterExpression::And(left, right) => match (&**left, &**right) {
+            (
+                FilterExpression::Condition(Condition::CreatedAt {
+                    comparison: Comparison::GreaterOrEqual,
+                    value: after,
+                }),
+                FilterExpression::Condition(Condition::CreatedAt {
+                    comparison: Comparison::Less,
+                    value: before,
+                }),
+            ) => created(Some(*after), Some(*before)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A UTC midnight as its date, anything else as the full timestamp.
+fn date(timestamp: Timestamp) -> String {
+    timestamp
+        .date()
+        .map_or_else(|| timestamp.to_string(), |date| date.to_string())
+}
+
+/// The strict check is the default, so it is left unsaid.
+fn non_default(mode: PalindromeMode) -> Option<PalindromeMode> {
+    (mode != PalindromeMode::default()).then_some(mode)
+}
+
+fn palindromes(mode: Option<PalindromeMode>, value: bool) -> String {
+    let mode = match mode {
+        None => "",
+        Some(PalindromeMode::Strict) => "strict ",
+        Some(PalindromeMode::Loose) => "loose ",
+        Some(PalindromeMode::Normalised) => "normalised ",
+    };
+
+    if value {
+        format!("{}palindromes", mode)
+    } else {
+        format!("{}non-palindromes", mode)
+    }
+}
+
+/// "containing the letter a" for one character, "containing a, b and c" for several.
+fn contains(characters: &[String]) -> String {
+    match characters {
+        [single] if is_letter(single) => format!("containing the letter {}", single),
+        characters => format!("containing {}", list(characters, "and")),
+    }
+}
+
+/// "containing e at least 2 times" for every `min_char_count`/`max_char_count` entry.
+fn character_counts(filters: &StringFilters) -> Vec<String> {
+    let min_counts = filters.min_char_count.clone().unwrap_or_default().0;
+    let max_counts = filters.max_char_count.clone().unwrap_or_default().0;
+
+    let mut characters = min_counts
+        .keys()
+        .chain(max_counts.keys())
+        .collect::<Vec<_>>();
+    characters.sort();
+    characters.dedup();
+
+    let times = |count: i32| if count == 1 { "time" } else { "times" };
+    let mut phrases = Vec::new();
+
+    for c in characters {
+        let containing = format!("containing {}", character(c));
+
+        match (min_counts.get(c), max_counts.get(c)) {
+            (Some(min), Some(max)) if min == max => {
+                phrases.push(format!("{} exactly {} {}", containing, min, times(*min)))
+            }
+            (min, max) => {
+                if let Some(min) = min {
+                    phrases.push(format!("{} at least {} {}", containing, min, times(*min)));
+                }
+                if let Some(max) = max {
+                    phrases.push(format!("{} at most {} {}", containing, max, times(*max)));
+                }
+            }
+        }
+    }
+
+    phrases
+}
+
+/// "a", "a or b", "a, b and c".
+fn list(characters: &[String], conjunction: &str) -> String {
+    let characters = characters
+        .iter()
+        .map(|c| character(c))
+        .collect::<Vec<String>>();
+
+    match characters.split_last() {
+        Some((last, rest)) if !rest.is_empty() => {
+            format!("{} {} {}", rest.join(", "), conjunction, last)
+        }
+        _ => characters.concat(),
+    }
+}
+
+/// Letters stay bare; anything else is quoted so the lexer reads it as one token.
+fn character(character: &str) -> String {
+    if is_letter(character) {
+        character.to_string()
+    } else if character == "'" {
+        "\"'\"".to_string()
+    } else {
+        format!("'{}'", character)
+    }
+}
+
+fn is_letter(character: &str) -> bool {
+    let mut chars = character.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if c.is_ascii_lowercase())
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::describe_filters;
+    use crate::{
+        models::filters::StringFilters,
+        utils::nlp::{NlpOptions, Vocabulary, parse_natural_language},
+    };
+
+    fn describe(filters: Value) -> String {
+        let mut filters: StringFilters = serde_json::from_value(filters).unwrap();
+        filters.parse_expression().unwrap();
+
+        describe_filters(&filters)
+    }
+
+    /// Describes `filters`, reads the description back and expects the same filters.
+    fn assert_round_trip(filters: Value) {
+        let mut filters: StringFilters = serde_json::from_value(filters).unwrap();
+        filters.parse_expression().unwrap();
+
+        let description = describe_filters(&filters);
+        let parsed =
+            parse_natural_language(&description, &NlpOptions::default(), &Vocabulary::default())
+                .unwrap_or_else(|e| panic!("'{}' does not parse: {}", description, e.reason));
+
+        assert_eq!(
+            parsed.filters.applied(),
+            filters.applied(),
+            "'{}' read back differently",
+            description
+        );
+    }
+
+    #[test]
+    fn lengths() {
+        for filters in [
+            json!({ "min_length": 6 }),
+            json!({ "max_length": 4 }),
+            json!({ "min_length": 5, "max_length": 5 }),
+            json!({ "min_length": 3, "max_length": 10 }),
+            json!({ "min_length": 1 }),
+            json!({ "max_length": 2147483647 }),
+            json!({ "min_length": 2, "length_unit": "bytes" }),
+            json!({ "min_length": 4, "max_length": 4, "length_unit": "codepoints" }),
+        ] {
+            assert_round_trip(filters);
+        }
+    }
+
+    #[test]
+    fn word_counts() {
+        for filters in [
+            json!({ "word_count": 1 }),
+            json!({ "word_count": 3 }),
+            json!({ "min_word_count": 2 }),
+            json!({ "max_word_count": 4 }),
+            json!({ "min_word_count": 2, "max_word_count": 5 }),
+            json!({ "filter": "NOT word_count:3" }),
+        ] {
+            assert_round_trip(filters);
+        }
+    }
+
+    #[test]
+    fn palindrome_modes() {
+        for filters in [
+            json!({ "is_palindrome": true }),
+            json!({ "is_palindrome": false }),
+            json!({ "is_palindrome": true, "palindrome_mode": "strict" }),
+            json!({ "is_palindrome": true, "palindrome_mode": "loose" }),
+            json!({ "is_palindrome": true, "palindrome_mode": "normalised" }),
+            json!({ "is_palindrome": false, "palindrome_mode": "loose" }),
+            json!({ "is_palindrome": true, "word_count": 1 }),
+        ] {
+            assert_round_trip(filters);
+        }
+    }
+
+    #[test]
+    fn contains() {
+        for filters in [
+            json!({ "contains_character": "a" }),
+            json!({ "contains_all": "abc" }),
+            json!({ "contains_any": "xyz" }),
+            json!({ "excludes": "z" }),
+            json!({ "excludes": "qz" }),
+            json!({ "contains_character": "a", "excludes": "b" }),
+            json!({ "min_char_count": "e:3" }),
+            json!({ "max_char_count": "e:2" }),
+            json!({ "min_char_count": "e:2", "max_char_count": "e:2" }),
+            json!({ "contains_any": "ab", "filter": "contains:c OR contains:d" }),
+        ] {
+            assert_round_trip(filters);
+        }
+    }
+
+    #[test]
+    fn character_classes() {
+        for filters in [
+            json!({ "min_digit_count": 1 }),
+            json!({ "max_digit_count": 0 }),
+            json!({ "digit_count": 2 }),
+            json!({ "min_uppercase_count": 3 }),
+            json!({ "max_punctuation_count": 2 }),
+            json!({ "min_whitespace_count": 1, "max_symbol_count": 0 }),
+            json!({ "emoji_count": 1 }),
+            json!({ "min_lowercase_count": 2, "max_lowercase_count": 4 }),
+        ] {
+            assert_round_trip(filters);
+        }
+    }
+
+    #[test]
+    fn vowels() {
+        for filters in [
+            json!({ "min_vowel_count": 1 }),
+            json!({ "max_vowel_count": 0 }),
+            json!({ "vowel_count": 3 }),
+            json!({ "min_consonant_count": 3 }),
+            json!({ "filter": "vowel_ratio:>0.5" }),
+            json!({ "filter": "vowel_ratio:<0.5 AND consonant_count:>=1" }),
+            json!({ "filter": "NOT vowel_ratio:>0.5" }),
+        ] {
+            assert_round_trip(filters);
+        }
+    }
+
+    #[test]
+    fn dates() {
+        for filters in [
+            json!({ "created_after": "2026-01-01" }),
+            json!({ "created_before": "2026-01-01" }),
+            json!({ "created_after": "2026-10-17", "created_before": "2026-10-18" }),
+            json!({ "created_after": "2026-03-01T12:30:00Z" }),
+        ] {
+            assert_round_trip(filters);
+        }
+    }
+
+    #[test]
+    fn ranges_in_or_clauses() {
+        for (filter, description) in [
+            (
+                "length:>=5 AND length:<=5 OR length:>=6 AND length:<=6",
+                "strings exactly 5 characters long or strings exactly 6 characters long",
+            ),
+            (
+                "is_palindrome:true AND word_count:>=2 AND word_count:<=3 OR contains:z",
+                "palindromes with between 2 and 3 words or strings containing the letter z",
+            ),
+            (
+                "digit_count:>=2 AND digit_count:<=4 OR is_palindrome:true",
+                "strings with between 2 and 4 digits or palindromes",
+            ),
+            (
+                "byte_length:>=4 AND byte_length:<=9 OR word_count:2",
+                "strings between 4 and 9 bytes long or strings with 2 words",
+            ),
+        ] {
+            assert_eq!(describe(json!({ "filter": filter })), description);
+            assert_round_trip(json!({ "filter": filter }));
+        }
+
+        // Bounds that are not next to each other still pair up.
+        assert_eq!(
+            describe(json!({
+                "filter": "length:<=9 AND is_palindrome:true AND length:>=4 OR contains:z"
+            })),
+            "palindromes between 4 and 9 characters long or strings containing the letter z"
+        );
+    }
+}
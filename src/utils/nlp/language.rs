@@ -0,0 +1,959 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+use utoipa::ToSchema;
+
+use crate::utils::nlp::{
+    lexer::{Token, TokenKind, union},
+    parser::{KEYWORDS, is_number_word},
+    vocabulary::Vocabulary,
+};
+
+/// Language a natural language query is written in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en", alias = "english")]
+    English,
+    #[serde(rename = "fr", alias = "french")]
+    French,
+    #[serde(rename = "es", alias = "spanish")]
+    Spanish,
+}
+
+const ENGLISH_ALPHABET: [char; 26] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+const SPANISH_ALPHABET: [char; 27] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'ñ', 'o', 'p', 'q', 'r',
+    's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+const ENGLISH_VOWELS: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
+/// French counts "y" among its vowels.
+const FRENCH_VOWELS: [char; 6] = ['a', 'e', 'i', 'o', 'u', 'y'];
+const ENGLISH_CONSONANTS: [char; 21] = [
+    'b', 'c', 'd', 'f', 'g', 'h', 'j', 'k', 'l', 'm', 'n', 'p', 'q', 'r', 's', 't', 'v', 'w', 'x',
+    'y', 'z',
+];
+const FRENCH_CONSONANTS: [char; 20] = [
+    'b', 'c', 'd', 'f', 'g', 'h', 'j', 'k', 'l', 'm', 'n', 'p', 'q', 'r', 's', 't', 'v', 'w', 'x',
+    'z',
+];
+const SPANISH_CONSONANTS: [char; 22] = [
+    'b', 'c', 'd', 'f', 'g', 'h', 'j', 'k', 'l', 'm', 'n', 'ñ', 'p', 'q', 'r', 's', 't', 'v', 'w',
+    'x', 'y', 'z',
+];
+
+impl Language {
+    /// Letters in alphabetical order, for "the fifth letter".
+    pub fn alphabet(&self) -> &'static [char] {
+        match self {
+            Language::English | Language::French => &ENGLISH_ALPHABET,
+            Language::Spanish => &SPANISH_ALPHABET,
+        }
+    }
+
+    pub fn vowels(&self) -> &'static [char] {
+        match self {
+            Language::English | Language::Spanish => &ENGLISH_VOWELS,
+            Language::French => &FRENCH_VOWELS,
+        }
+    }
+
+    pub fn consonants(&self) -> &'static [char] {
+        match self {
+            Language::English => &ENGLISH_CONSONANTS,
+            Language::French => &FRENCH_CONSONANTS,
+            Language::Spanish => &SPANISH_CONSONANTS,
+        }
+    }
+
+    /// Whether `word` is one the parser skips in this language without lowering the
+    /// confidence, on top of the English filler words.
+    pub fn is_filler(&self, word: &str) -> bool {
+        self.lexicon()
+            .is_some_and(|lexicon| lexicon.filler.contains(&lexicon.key(word).as_str()))
+    }
+
+    fn lexicon(&self) -> Option<&'static Lexicon> {
+        match self {
+            Language::English => None,
+            Language::French => Some(&FRENCH),
+            Language::Spanish => Some(&SPANISH),
+        }
+    }
+}
+
+/// How a language's words map onto the English keywords the parser reads. Phrases are
+/// matched on words with their accents removed, longest first, so "plus longues que"
+/// (longer than) wins over "plus longues" (longest).
+struct Lexicon {
+    phrases: &'static [(&'static str, &'static str)],
+    /// Words kept as typed and skipped like English filler: "qui", "que".
+    filler: &'static [&'static str],
+    /// Elided articles and pronouns stripped before a lookup: "l'année".
+    elisions: &'static [&'static str],
+    /// Suffixes of ordinals written with digits: "3e", "3º".
+    ordinal_suffixes: &'static [&'static str],
+    /// Joins tens and units: "treinta y dos", "vingt et un".
+    and: &'static str,
+}
+
+impl Lexicon {
+    /// `word` as it is looked up: without an elided prefix or accents.
+    fn key(&self, word: &str) -> String {
+        let word = word.replace('\u{2019}', "'");
+        let word = self
+            .elisions
+            .iter()
+            .find_map(|elision| word.strip_prefix(elision).filter(|rest| !rest.is_empty()))
+            .unwrap_or(&word);
+
+        word.nfd().filter(|c| !is_combining_mark(*c)).collect()
+    }
+
+    fn knows(&self, key: &str) -> bool {
+        self.filler.contains(&key)
+            || self
+                .phrases
+                .iter()
+                .any(|(phrase, _)| phrase.split(' ').any(|word| self.key(word) == key))
+    }
+
+    /// The English for the longest phrase starting at `keys[0]`, and how many words it took.
+    fn phrase(&self, keys: &[Option<String>]) -> Option<(&'static str, usize)> {
+        self.phrases
+            .iter()
+            .filter_map(|(phrase, english)| {
+                let mut length = 0;
+                for word in phrase.split(' ') {
+                    if keys.get(length)?.as_deref()? != self.key(word) {
+                        return None;
+                    }
+                    length += 1;
+                }
+                Some((*english, length))
+            })
+            .max_by_key(|(_, length)| *length)
+    }
+
+    /// A number or ordinal in one word that the phrase table does not list: "15e",
+    /// "vingt-deux", "3º".
+    fn number(&self, key: &str) -> Option<String> {
+        if let Some(digits) = self
+            .ordinal_suffixes
+            .iter()
+            .find_map(|suffix| key.strip_suffix(suffix))
+            .filter(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Some(format!("{}th", digits));
+        }
+
+        // "vingt-deux", "vingt-et-un": translate each part, dropping the "and".
+        let parts = key
+            .split('-')
+            .filter(|part| *part != self.and)
+            .map(|part| self.word(part))
+            .collect::<Option<Vec<&str>>>()?;
+        let english = parts.join("-");
+
+        (parts.len() > 1 && is_number_word(&english)).then_some(english)
+    }
+
+    /// The English for a single word listed on its own.
+    fn word(&self, key: &str) -> Option<&'static str> {
+        self.phrases
+            .iter()
+            .find(|(phrase, _)| self.key(phrase) == key)
+            .map(|(_, english)| *english)
+    }
+}
+
+const ENGLISH_TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const ENGLISH_UNITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const LETTER_WORDS: [&str; 4] = ["letter", "letters", "character", "characters"];
+const SUPERLATIVES: [&str; 4] = ["longest", "shortest", "newest", "oldest"];
+/// How many words "ne … pas" may enclose: "ne contiennent pas", "ne le sont pas".
+const NEGATED_WORDS: usize = 2;
+const NOUNS: [&str; 6] = [
+    "string",
+    "strings",
+    "palindrome",
+    "palindromes",
+    "value",
+    "values",
+];
+
+/// Picks the language most of the query's words belong to; English wins ties.
+pub fn detect_language(tokens: &[Token], vocabulary: &Vocabulary) -> Language {
+    let words = tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Word)
+        .map(|token| token.text.as_str())
+        .collect::<Vec<&str>>();
+
+    let english = words
+        .iter()
+        .filter(|word| {
+            vocabulary.keywords().any(|(_, keyword)| keyword == **word)
+                || vocabulary.filler.iter().any(|filler| filler == *word)
+                || KEYWORDS.contains(word)
+                || is_number_word(word)
+        })
+        .count();
+    let score = |language: Language| {
+        let lexicon = language.lexicon().unwrap();
+        words
+            .iter()
+            .filter(|word| lexicon.knows(&lexicon.key(word)))
+            .count()
+    };
+
+    [Language::French, Language::Spanish]
+        .into_iter()
+        .map(|language| (language, score(language)))
+        .filter(|(_, score)| *score > english)
+        .fold((Language::English, english), |best, candidate| {
+            match candidate.1.cmp(&best.1) {
+                std::cmp::Ordering::Greater => candidate,
+                // Just as much French as Spanish: neither is a safe guess.
+                std::cmp::Ordering::Equal => (Language::English, best.1),
+                std::cmp::Ordering::Less => best,
+            }
+        })
+        .0
+}
+
+/// Rewrites a French or Spanish query as the English keywords the parser reads, e.g.
+/// "plus longues que 5" as "longer than 5". Each English token keeps the span of the words
+/// it came from; words with no translation are kept as typed.
+pub fn translate(tokens: &[Token], language: Language) -> Vec<Token> {
+    let Some(lexicon) = language.lexicon() else {
+        return tokens.to_vec();
+    };
+
+    let keys = tokens
+        .iter()
+        .map(|token| (token.kind == TokenKind::Word).then(|| lexicon.key(&token.text)))
+        .collect::<Vec<Option<String>>>();
+    let single = |index: usize| -> Option<String> {
+        let key = keys.get(index)?.as_deref()?;
+        match lexicon.phrase(&keys[index..index + 1]) {
+            Some((english, 1)) => Some(english.to_string()),
+            _ => lexicon.number(key),
+        }
+    };
+
+    let mut translated = Vec::new();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = &tokens[index];
+
+        // "la letra y" and "sin a ni e" name letters, not "and".
+        if keys[index].is_none()
+            || (token.text.chars().count() == 1 && names_character(&translated))
+        {
+            translated.push(token.clone());
+            index += 1;
+            continue;
+        }
+
+        // "treinta y dos", "vingt et un"
+        if let (Some(tens), Some(and), Some(unit)) =
+            (single(index), keys.get(index + 1), single(index + 2))
+            && ENGLISH_TENS.contains(&tens.as_str())
+            && and.as_deref() == Some(lexicon.and)
+            && ENGLISH_UNITS.contains(&unit.as_str())
+        {
+            translated.push(Token {
+                kind: TokenKind::Word,
+                text: format!("{}-{}", tens, unit),
+                span: union(&tokens[index..index + 3]),
+            });
+            index += 3;
+            continue;
+        }
+
+        if let Some((english, length)) = lexicon.phrase(&keys[index..]) {
+            // "a" is no article in French or Spanish: "con a" is the letter a.
+            let single_character = tokens
+                .get(index + length)
+                .is_some_and(|token| token.text.chars().count() == 1);
+            let english = match english {
+                "with" if single_character => "containing",
+                english => english,
+            };
+            let words = english.split(' ').collect::<Vec<&str>>();
+            let source = &tokens[index..index + length];
+            for (position, word) in words.iter().enumerate() {
+                translated.push(Token {
+                    kind: TokenKind::Word,
+                    text: word.to_string(),
+                    // Word for word when the counts match, otherwise the whole phrase.
+                    span: match words.len() == length {
+                        true => source[position].span,
+                        false => union(source),
+                    },
+                });
+            }
+            index += length;
+            continue;
+        }
+
+        let text = keys[index]
+            .as_deref()
+            .and_then(|key| lexicon.number(key))
+            .unwrap_or_else(|| token.text.clone());
+        translated.push(Token {
+            text,
+            ..token.clone()
+        });
+        index += 1;
+    }
+
+    reorder(&mut translated);
+    translated
+}
+
+/// Whether a single character after the words translated so far is a character rather than
+/// a word: after "letter" or "containing", or in a list such as "a, b or c".
+fn names_character(translated: &[Token]) -> bool {
+    let text = |back: usize| {
+        translated
+            .len()
+            .checked_sub(back)
+            .map(|index| translated[index].text.as_str())
+    };
+
+    match text(1) {
+        Some(word) if LETTER_WORDS.contains(&word) => true,
+        Some("containing" | "without") => true,
+        Some("and" | "or" | ",") => text(2).is_some_and(|word| word.chars().count() == 1),
+        _ => false,
+    }
+}
+
+/// Moves words into English order: "3 last days" ("les 3 derniers jours") becomes "last 3
+/// days", "strings the longest" ("les chaînes les plus longues") becomes "longest strings
+/// the", and "ne containing not de a" ("ne contenant pas de a") becomes "ne not de
+/// containing a". Tokens moved past a number or noun share the span of the words they were
+/// moved across; a negation keeps its own.
+fn reorder(tokens: &mut [Token]) {
+    let mut index = 0;
+
+    while index < tokens.len() {
+        if tokens[index].text == "not"
+            && let Some(ne) = (index.saturating_sub(NEGATED_WORDS + 1)..index)
+                .rev()
+                .find(|position| tokens[*position].text == "ne")
+        {
+            // "pas de a" moves as one: "de" would otherwise be read as the character.
+            let partitive = tokens
+                .get(index + 1)
+                .is_some_and(|token| token.text == "de");
+            let end = index + 1 + usize::from(partitive);
+            tokens[ne + 1..end].rotate_right(end - index);
+            index = end;
+            continue;
+        }
+
+        if index + 1 == tokens.len() {
+            break;
+        }
+
+        let number = matches!(tokens[index].kind, TokenKind::Number(_))
+            || is_number_word(&tokens[index].text);
+
+        if number && tokens[index + 1].text == "last" {
+            tokens[index..index + 2].rotate_left(1);
+            let span = union(&tokens[index..index + 2]);
+            tokens[index..index + 2]
+                .iter_mut()
+                .for_each(|token| token.span = span);
+            index += 2;
+            continue;
+        }
+
+        if NOUNS.contains(&tokens[index].text.as_str()) {
+            let article = usize::from(tokens[index + 1].text == "the");
+            if tokens
+                .get(index + 1 + article)
+                .is_some_and(|token| SUPERLATIVES.contains(&token.text.as_str()))
+            {
+                let end = index + 2 + article;
+                tokens[index..end].rotate_right(1);
+                let span = union(&tokens[index..end]);
+                tokens[index..end]
+                    .iter_mut()
+                    .for_each(|token| token.span = span);
+                index = end;
+                continue;
+            }
+        }
+
+        index += 1;
+    }
+}
+
+const FRENCH: Lexicon = Lexicon {
+    phrases: &[
+        // Articles and nouns
+        ("le", "the"),
+        ("la", "the"),
+        ("les", "the"),
+        ("des", "the"),
+        ("du", "the"),
+        ("un", "one"),
+        ("une", "one"),
+        ("chaîne", "string"),
+        ("chaînes", "strings"),
+        ("texte", "text"),
+        ("textes", "texts"),
+        ("valeur", "value"),
+        ("valeurs", "values"),
+        ("entrée", "entry"),
+        ("entrées", "entries"),
+        // Palindromes and negation
+        ("palindrome", "palindrome"),
+        ("palindromes", "palindromes"),
+        ("palindromique", "palindromic"),
+        ("palindromiques", "palindromic"),
+        ("non-palindromique", "non-palindromic"),
+        ("non-palindromiques", "non-palindromic"),
+        ("pas", "not"),
+        // Comparisons
+        ("plus long que", "longer than"),
+        ("plus longue que", "longer than"),
+        ("plus longs que", "longer than"),
+        ("plus longues que", "longer than"),
+        ("plus grand que", "bigger than"),
+        ("plus grande que", "bigger than"),
+        ("plus grands que", "bigger than"),
+        ("plus grandes que", "bigger than"),
+        ("plus court que", "shorter than"),
+        ("plus courte que", "shorter than"),
+        ("plus courts que", "shorter than"),
+        ("plus courtes que", "shorter than"),
+        ("plus petit que", "smaller than"),
+        ("plus petite que", "smaller than"),
+        ("plus petits que", "smaller than"),
+        ("plus petites que", "smaller than"),
+        ("plus de", "more than"),
+        ("plus que", "more than"),
+        ("moins de", "fewer than"),
+        ("moins que", "less than"),
+        ("au moins", "at least"),
+        ("au minimum", "at least"),
+        ("au plus", "at most"),
+        ("au maximum", "at most"),
+        ("exactement", "exactly"),
+        ("égal à", "equals"),
+        ("égale à", "equals"),
+        ("entre", "between"),
+        ("et", "and"),
+        ("ou", "or"),
+        ("ni", "or"),
+        ("environ", "about"),
+        ("approximativement", "about"),
+        ("à peu près", "about"),
+        ("autour de", "about"),
+        ("près de", "about"),
+        // Measures
+        ("caractère", "character"),
+        ("caractères", "characters"),
+        ("lettre", "letter"),
+        ("lettres", "letters"),
+        ("mot", "word"),
+        ("mots", "words"),
+        ("octet", "byte"),
+        ("octets", "bytes"),
+        ("graphème", "grapheme"),
+        ("graphèmes", "graphemes"),
+        ("point de code", "code point"),
+        ("points de code", "code points"),
+        ("un seul mot", "single word"),
+        ("seul mot", "single word"),
+        ("mot unique", "single word"),
+        // Characters
+        ("avec", "with"),
+        ("sans", "without"),
+        ("contenant", "containing"),
+        ("contient", "contains"),
+        ("contiennent", "contain"),
+        ("excluant", "excluding"),
+        ("aucun", "no"),
+        ("aucune", "no"),
+        ("fois", "times"),
+        ("une fois", "once"),
+        ("une seule fois", "once"),
+        ("deux fois", "twice"),
+        ("trois fois", "thrice"),
+        ("voyelle", "vowel"),
+        ("consonne", "consonant"),
+        ("lettre de l'alphabet", "letter"),
+        // Sorting
+        ("plus long", "longest"),
+        ("plus longue", "longest"),
+        ("plus longs", "longest"),
+        ("plus longues", "longest"),
+        ("plus court", "shortest"),
+        ("plus courte", "shortest"),
+        ("plus courts", "shortest"),
+        ("plus courtes", "shortest"),
+        ("plus récent", "newest"),
+        ("plus récente", "newest"),
+        ("plus récents", "newest"),
+        ("plus récentes", "newest"),
+        ("plus ancien", "oldest"),
+        ("plus ancienne", "oldest"),
+        ("plus anciens", "oldest"),
+        ("plus anciennes", "oldest"),
+        ("plus vieux", "oldest"),
+        ("plus vieille", "oldest"),
+        ("plus vieilles", "oldest"),
+        ("le plus de", "most"),
+        ("le moins de", "fewest"),
+        ("caractères uniques", "unique characters"),
+        ("caractères distincts", "unique characters"),
+        ("lettres uniques", "unique letters"),
+        ("ordre alphabétique", "alphabetical"),
+        ("alphabétique", "alphabetical"),
+        ("alphabétiquement", "alphabetically"),
+        // Dates
+        ("aujourd'hui", "today"),
+        ("hier", "yesterday"),
+        ("cette semaine", "this week"),
+        ("ce mois", "this month"),
+        ("ce mois-ci", "this month"),
+        ("cette année", "this year"),
+        ("semaine dernière", "last week"),
+        ("semaine passée", "last week"),
+        ("mois dernier", "last month"),
+        ("mois passé", "last month"),
+        ("année dernière", "last year"),
+        ("année passée", "last year"),
+        ("ajouté", "added"),
+        ("ajoutée", "added"),
+        ("ajoutés", "added"),
+        ("ajoutées", "added"),
+        ("créé", "created"),
+        ("créée", "created"),
+        ("créés", "created"),
+        ("créées", "created"),
+        ("depuis", "since"),
+        ("depuis le", "since"),
+        ("depuis la", "since"),
+        ("avant", "before"),
+        ("avant le", "before"),
+        ("avant la", "before"),
+        ("après", "after"),
+        ("après le", "after"),
+        ("après la", "after"),
+        ("pendant", "during"),
+        ("au cours de", "during"),
+        ("au cours des", "during the"),
+        ("dernier", "last"),
+        ("dernière", "last"),
+        ("derniers", "last"),
+        ("dernières", "last"),
+        ("heure", "hour"),
+        ("heures", "hours"),
+        ("jour", "day"),
+        ("jours", "days"),
+        ("semaine", "week"),
+        ("semaines", "weeks"),
+        ("mois", "months"),
+        ("an", "year"),
+        ("ans", "years"),
+        ("année", "year"),
+        ("années", "years"),
+        // Numbers
+        ("zéro", "zero"),
+        ("deux", "two"),
+        ("trois", "three"),
+        ("quatre", "four"),
+        ("cinq", "five"),
+        ("six", "six"),
+        ("sept", "seven"),
+        ("huit", "eight"),
+        ("neuf", "nine"),
+        ("dix", "ten"),
+        ("onze", "eleven"),
+        ("douze", "twelve"),
+        ("treize", "thirteen"),
+        ("quatorze", "fourteen"),
+        ("quinze", "fifteen"),
+        ("seize", "sixteen"),
+        ("dix-sept", "seventeen"),
+        ("dix-huit", "eighteen"),
+        ("dix-neuf", "nineteen"),
+        ("vingt", "twenty"),
+        ("trente", "thirty"),
+        ("quarante", "forty"),
+        ("cinquante", "fifty"),
+        ("soixante", "sixty"),
+        ("soixante-dix", "seventy"),
+        ("quatre-vingt", "eighty"),
+        ("quatre-vingts", "eighty"),
+        ("quatre-vingt-dix", "ninety"),
+        ("cent", "hundred"),
+        ("cents", "hundred"),
+        ("mille", "thousand"),
+        ("million", "million"),
+        ("millions", "million"),
+        // Ordinals
+        ("premier", "first"),
+        ("première", "first"),
+        ("deuxième", "second"),
+        ("second", "second"),
+        ("seconde", "second"),
+        ("troisième", "third"),
+        ("quatrième", "fourth"),
+        ("cinquième", "fifth"),
+        ("sixième", "sixth"),
+        ("septième", "seventh"),
+        ("huitième", "eighth"),
+        ("neuvième", "ninth"),
+        ("dixième", "tenth"),
+        ("onzième", "eleventh"),
+        ("douzième", "twelfth"),
+        ("treizième", "thirteenth"),
+        ("quatorzième", "fourteenth"),
+        ("quinzième", "fifteenth"),
+        ("seizième", "sixteenth"),
+        ("dix-septième", "seventeenth"),
+        ("dix-huitième", "eighteenth"),
+        ("dix-neuvième", "nineteenth"),
+        ("vingtième", "twentieth"),
+    ],
+    filler: &[
+        "qui",
+        "que",
+        "ne",
+        "sont",
+        "est",
+        "ont",
+        "a",
+        "ayant",
+        "de",
+        "en",
+        "tous",
+        "toutes",
+        "tout",
+        "toute",
+        "montre",
+        "montrez",
+        "moi",
+        "trouve",
+        "trouvez",
+        "liste",
+        "listez",
+        "donne",
+        "donnez",
+        "dont",
+        "par",
+        "ordre",
+        "seulement",
+        "uniquement",
+    ],
+    elisions: &["l'", "d'", "qu'", "n'", "j'", "s'", "c'"],
+    ordinal_suffixes: &["ere", "er", "re", "eme", "e"],
+    and: "et",
+};
+
+const SPANISH: Lexicon = Lexicon {
+    phrases: &[
+        // Articles and nouns
+        ("el", "the"),
+        ("la", "the"),
+        ("los", "the"),
+        ("las", "the"),
+        ("lo", "the"),
+        ("un", "one"),
+        ("una", "one"),
+        ("uno", "one"),
+        ("cadena", "string"),
+        ("cadenas", "strings"),
+        ("texto", "text"),
+        ("textos", "texts"),
+        ("valor", "value"),
+        ("valores", "values"),
+        ("entrada", "entry"),
+        ("entradas", "entries"),
+        // Palindromes and negation
+        ("palíndromo", "palindrome"),
+        ("palíndromos", "palindromes"),
+        ("palíndroma", "palindrome"),
+        ("palíndromas", "palindromes"),
+        ("capicúa", "palindrome"),
+        ("capicúas", "palindromes"),
+        ("no", "not"),
+        // Comparisons
+        ("más largo que", "longer than"),
+        ("más larga que", "longer than"),
+        ("más largos que", "longer than"),
+        ("más largas que", "longer than"),
+        ("más grande que", "bigger than"),
+        ("más grandes que", "bigger than"),
+        ("más corto que", "shorter than"),
+        ("más corta que", "shorter than"),
+        ("más cortos que", "shorter than"),
+        ("más cortas que", "shorter than"),
+        ("más pequeño que", "smaller than"),
+        ("más pequeña que", "smaller than"),
+        ("más pequeños que", "smaller than"),
+        ("más pequeñas que", "smaller than"),
+        ("más de", "more than"),
+        ("más que", "more than"),
+        ("menos de", "fewer than"),
+        ("menos que", "less than"),
+        ("al menos", "at least"),
+        ("por lo menos", "at least"),
+        ("como mínimo", "at least"),
+        ("como máximo", "at most"),
+        ("como mucho", "at most"),
+        ("a lo sumo", "at most"),
+        ("exactamente", "exactly"),
+        ("igual a", "equals"),
+        ("entre", "between"),
+        ("y", "and"),
+        ("e", "and"),
+        ("o", "or"),
+        ("u", "or"),
+        ("ni", "or"),
+        ("aproximadamente", "about"),
+        ("alrededor de", "about"),
+        ("cerca de", "about"),
+        ("unos", "about"),
+        ("unas", "about"),
+        // Measures
+        ("carácter", "character"),
+        ("caracteres", "characters"),
+        ("letra", "letter"),
+        ("letras", "letters"),
+        ("palabra", "word"),
+        ("palabras", "words"),
+        ("grafema", "grapheme"),
+        ("grafemas", "graphemes"),
+        ("punto de código", "code point"),
+        ("puntos de código", "code points"),
+        ("una sola palabra", "single word"),
+        ("una palabra", "single word"),
+        ("sola palabra", "single word"),
+        // Characters
+        ("con", "with"),
+        ("sin", "without"),
+        ("conteniendo", "containing"),
+        ("contiene", "contains"),
+        ("contienen", "contain"),
+        ("contenga", "contains"),
+        ("contengan", "contain"),
+        ("excluyendo", "excluding"),
+        ("ninguna", "no"),
+        ("ningún", "no"),
+        ("vez", "time"),
+        ("veces", "times"),
+        ("una vez", "once"),
+        ("una sola vez", "once"),
+        ("dos veces", "twice"),
+        ("tres veces", "thrice"),
+        ("vocal", "vowel"),
+        ("vocales", "vowel"),
+        ("consonante", "consonant"),
+        ("consonantes", "consonant"),
+        ("letra del alfabeto", "letter"),
+        // Sorting
+        ("más largo", "longest"),
+        ("más larga", "longest"),
+        ("más largos", "longest"),
+        ("más largas", "longest"),
+        ("más corto", "shortest"),
+        ("más corta", "shortest"),
+        ("más cortos", "shortest"),
+        ("más cortas", "shortest"),
+        ("más reciente", "newest"),
+        ("más recientes", "newest"),
+        ("más antiguo", "oldest"),
+        ("más antigua", "oldest"),
+        ("más antiguos", "oldest"),
+        ("más antiguas", "oldest"),
+        ("más viejo", "oldest"),
+        ("más vieja", "oldest"),
+        ("más viejos", "oldest"),
+        ("más viejas", "oldest"),
+        ("más", "most"),
+        ("menos", "fewest"),
+        ("caracteres únicos", "unique characters"),
+        ("caracteres distintos", "unique characters"),
+        ("letras únicas", "unique letters"),
+        ("orden alfabético", "alphabetical"),
+        ("alfabético", "alphabetical"),
+        ("alfabéticamente", "alphabetically"),
+        // Dates
+        ("hoy", "today"),
+        ("ayer", "yesterday"),
+        ("esta semana", "this week"),
+        ("este mes", "this month"),
+        ("este año", "this year"),
+        ("semana pasada", "last week"),
+        ("mes pasado", "last month"),
+        ("año pasado", "last year"),
+        ("agregado", "added"),
+        ("agregada", "added"),
+        ("agregados", "added"),
+        ("agregadas", "added"),
+        ("añadido", "added"),
+        ("añadida", "added"),
+        ("añadidos", "added"),
+        ("añadidas", "added"),
+        ("creado", "created"),
+        ("creada", "created"),
+        ("creados", "created"),
+        ("creadas", "created"),
+        ("desde", "since"),
+        ("desde el", "since"),
+        ("desde la", "since"),
+        ("antes de", "before"),
+        ("antes del", "before"),
+        ("antes de la", "before"),
+        ("antes", "before"),
+        ("después de", "after"),
+        ("después del", "after"),
+        ("después de la", "after"),
+        ("durante", "during"),
+        ("último", "last"),
+        ("última", "last"),
+        ("últimos", "last"),
+        ("últimas", "last"),
+        ("hora", "hour"),
+        ("horas", "hours"),
+        ("día", "day"),
+        ("días", "days"),
+        ("semana", "week"),
+        ("semanas", "weeks"),
+        ("mes", "month"),
+        ("meses", "months"),
+        ("año", "year"),
+        ("años", "years"),
+        // Numbers
+        ("cero", "zero"),
+        ("dos", "two"),
+        ("tres", "three"),
+        ("cuatro", "four"),
+        ("cinco", "five"),
+        ("seis", "six"),
+        ("siete", "seven"),
+        ("ocho", "eight"),
+        ("nueve", "nine"),
+        ("diez", "ten"),
+        ("once", "eleven"),
+        ("doce", "twelve"),
+        ("trece", "thirteen"),
+        ("catorce", "fourteen"),
+        ("quince", "fifteen"),
+        ("dieciséis", "sixteen"),
+        ("diecisiete", "seventeen"),
+        ("dieciocho", "eighteen"),
+        ("diecinueve", "nineteen"),
+        ("veinte", "twenty"),
+        ("veintiuno", "twenty-one"),
+        ("veintiún", "twenty-one"),
+        ("veintiuna", "twenty-one"),
+        ("veintidós", "twenty-two"),
+        ("veintitrés", "twenty-three"),
+        ("veinticuatro", "twenty-four"),
+        ("veinticinco", "twenty-five"),
+        ("veintiséis", "twenty-six"),
+        ("veintisiete", "twenty-seven"),
+        ("veintiocho", "twenty-eight"),
+        ("veintinueve", "twenty-nine"),
+        ("treinta", "thirty"),
+        ("cuarenta", "forty"),
+        ("cincuenta", "fifty"),
+        ("sesenta", "sixty"),
+        ("setenta", "seventy"),
+        ("ochenta", "eighty"),
+        ("noventa", "ninety"),
+        ("cien", "hundred"),
+        ("ciento", "hundred"),
+        ("mil", "thousand"),
+        ("millón", "million"),
+        ("millones", "million"),
+        // Ordinals
+        ("primero", "first"),
+        ("primera", "first"),
+        ("primer", "first"),
+        ("segundo", "second"),
+        ("segunda", "second"),
+        ("tercero", "third"),
+        ("tercera", "third"),
+        ("tercer", "third"),
+        ("cuarto", "fourth"),
+        ("cuarta", "fourth"),
+        ("quinto", "fifth"),
+        ("quinta", "fifth"),
+        ("sexto", "sixth"),
+        ("sexta", "sixth"),
+        ("séptimo", "seventh"),
+        ("séptima", "seventh"),
+        ("octavo", "eighth"),
+        ("octava", "eighth"),
+        ("noveno", "ninth"),
+        ("novena", "ninth"),
+        ("décimo", "tenth"),
+        ("décima", "tenth"),
+        ("undécimo", "eleventh"),
+        ("undécima", "eleventh"),
+        ("duodécimo", "twelfth"),
+        ("duodécima", "twelfth"),
+        ("decimotercero", "thirteenth"),
+        ("decimotercera", "thirteenth"),
+        ("decimocuarto", "fourteenth"),
+        ("decimocuarta", "fourteenth"),
+        ("decimoquinto", "fifteenth"),
+        ("decimoquinta", "fifteenth"),
+        ("decimosexto", "sixteenth"),
+        ("decimosexta", "sixteenth"),
+        ("decimoséptimo", "seventeenth"),
+        ("decimoséptima", "seventeenth"),
+        ("decimoctavo", "eighteenth"),
+        ("decimoctava", "eighteenth"),
+        ("decimonoveno", "nineteenth"),
+        ("decimonovena", "nineteenth"),
+        ("vigésimo", "twentieth"),
+        ("vigésima", "twentieth"),
+    ],
+    filler: &[
+        "que",
+        "son",
+        "es",
+        "sean",
+        "tienen",
+        "tiene",
+        "tengan",
+        "todas",
+        "todos",
+        "muestra",
+        "muestrame",
+        "encuentra",
+        "lista",
+        "dame",
+        "de",
+        "del",
+        "en",
+        "cuyo",
+        "cuya",
+        "solo",
+        "solamente",
+        "por",
+        "orden",
+    ],
+    elisions: &[],
+    ordinal_suffixes: &[".º", ".ª", "º", "ª", "°"],
+    and: "y",
+};
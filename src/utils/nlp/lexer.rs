@@ -0,0 +1,96 @@
+use crate::models::nlp::TextSpan;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Word,
+    Number(i32),
+    /// Text between matching `'` or `"` quotes, e.g. `'a'`.
+    Quoted,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Lowercased, without surrounding quotes or trailing sentence punctuation.
+    pub text: String,
+    pub span: TextSpan,
+}
+
+/// Splits a query into words, numbers, quoted text and commas, keeping each token's
+/// character span in the original query.
+pub fn tokenise(query: &str) -> Vec<Token> {
+    let chars = query.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let start = index;
+
+        if chars[index].is_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        if chars[index] == ',' {
+            index += 1;
+            tokens.push(Token {
+                kind: TokenKind::Comma,
+                text: ",".to_string(),
+                span: TextSpan { start, end: index },
+            });
+            continue;
+        }
+
+        if matches!(chars[index], '\'' | '"')
+            && let Some(length) = chars[index + 1..].iter().position(|c| *c == chars[start])
+        {
+            index += length + 2;
+            tokens.push(Token {
+                kind: TokenKind::Quoted,
+                text: chars[start + 1..index - 1]
+                    .iter()
+                    .collect::<String>()
+                    .to_lowercase(),
+                span: TextSpan { start, end: index },
+            });
+            continue;
+        }
+
+        while index < chars.len() && !chars[index].is_whitespace() && chars[index] != ',' {
+            index += 1;
+        }
+
+        let mut end = index;
+        while end - start > 1 && matches!(chars[end - 1], '.' | '?' | '!' | ';' | ':') {
+            end -= 1;
+        }
+
+        let text = chars[start..end].iter().collect::<String>().to_lowercase();
+        let kind = match text.parse::<i32>() {
+            Ok(number) => TokenKind::Number(number),
+            Err(_) => TokenKind::Word,
+        };
+
+        tokens.push(Token {
+            kind,
+            text,
+            span: TextSpan { start, end },
+        });
+    }
+
+    tokens
+}
+
+/// Span from the first of `tokens` to the end of the last, in query order; a translated
+/// query may have moved a token past others.
+pub fn union(tokens: &[Token]) -> TextSpan {
+    TextSpan {
+        start: tokens
+            .iter()
+            .map(|token| token.span.start)
+            .min()
+            .unwrap_or(0),
+        end: tokens.iter().map(|token| token.span.end).max().unwrap_or(0),
+    }
+}
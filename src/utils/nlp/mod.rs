@@ -0,0 +1,883 @@
+mod describe;
+mod language;
+mod lexer;
+mod parser;
+mod spelling;
+mod suggest;
+mod vocabulary;
+
+use std::{ops::Range, sync::LazyLock};
+
+use chrono::{DateTime, Utc};
+pub use describe::describe_filters;
+pub use language::Language;
+pub use suggest::suggest_completions;
+pub use vocabulary::{LengthAdjective, LoadedVocabulary, Vocabulary, VocabularyStore};
+
+use crate::{
+    models::{
+        expression::{Comparison, Condition, FilterExpression},
+        filters::{SortField, StringFilters, Timestamp, character_set},
+        nlp::{
+            AlternativeInterpretation, Correction, InterpretedFilter, NlpError, QueryToken,
+            TextSpan,
+        },
+    },
+    utils::nlp::{
+        language::{detect_language, translate},
+        lexer::{Token, tokenise, union},
+        parser::{AmbiguityKind, Parser, RuleMatch},
+        spelling::correct_spelling,
+    },
+};
+
+pub const DEFAULT_APPROXIMATE_TOLERANCE: u32 = 20;
+
+/// Settings for `parse_natural_language`.
+#[derive(Debug, Clone, Copy)]
+pub struct NlpOptions {
+    /// How far "about 10 characters" may stray from 10, as a percentage of the number.
+    pub approximate_tolerance: u32,
+    /// Language of the query; detected from its words when `None`.
+    pub language: Option<Language>,
+}
+
+impl Default for NlpOptions {
+    fn default() -> Self {
+        Self {
+            approximate_tolerance: DEFAULT_APPROXIMATE_TOLERANCE,
+            language: None,
+        }
+    }
+}
+
+static DEFAULT_VOCABULARY: LazyLock<Vocabulary> = LazyLock::new(Vocabulary::default);
+
+/// Confidence is multiplied by this for every unused token.
+const UNUSED_TOKEN_CONFIDENCE: f32 = 0.9;
+/// And by this for every misspelt keyword.
+const CORRECTION_CONFIDENCE: f32 = 0.9;
+
+pub struct ParsedQuery {
+    pub original: String,
+    pub language: Language,
+    pub filters: StringFilters,
+    pub confidence: f32,
+    pub interpreted_filters: Vec<InterpretedFilter>,
+    pub alternatives: Vec<AlternativeInterpretation>,
+    pub consumed_tokens: Vec<QueryToken>,
+    pub ignored_tokens: Vec<QueryToken>,
+    pub unused_tokens: Vec<QueryToken>,
+    pub corrections: Vec<Correction>,
+}
+
+/// Filters read from one "or"-separated clause, plus the conditions flat `StringFilters`
+/// cannot hold: negations ("not exactly 5 characters") and comparisons between two counts
+/// ("more vowels than consonants").
+#[derive(Default)]
+struct Clause {
+    filters: StringFilters,
+    expressions: Vec<FilterExpression>,
+    /// Indices of the clause's tokens.
+    tokens: Range<usize>,
+}
+
+/// Parses a query such as "palindromes or single words", "not palindromes" or "strings
+/// between 5 and 10 characters" with the grammar in `parser`. A query with "or" clauses or
+/// negations that flat filters cannot express is returned as a `filter` expression,
+/// alongside any sort.
+///
+/// Each matched phrase is reported with a confidence, and every ambiguous phrase with the
+/// filters the query gives when read the other way. Keywords come from `vocabulary`;
+/// misspelt ones are read as the closest keyword and reported. French and Spanish queries
+/// ("plus longues que 5", "palíndromos") give the same filters as their English reading.
+pub fn parse_natural_language(
+    query: &str,
+    options: &NlpOptions,
+    vocabulary: &Vocabulary,
+) -> Result<ParsedQuery, NlpError> {
+    let tokens = tokenise(query);
+    let language = options
+        .language
+        .unwrap_or_else(|| detect_language(&tokens, vocabulary));
+    let options = &NlpOptions {
+        language: Some(language),
+        ..*options
+    };
+
+    // French and Spanish are read as the English keywords they translate to, so the
+    // configured vocabulary only applies to English.
+    let typed = tokens;
+    let (mut tokens, vocabulary) = match language {
+        Language::English => (typed.clone(), vocabulary),
+        _ => (translate(&typed, language), &*DEFAULT_VOCABULARY),
+    };
+    let corrections = match language {
+        Language::English => correct_spelling(&mut tokens, vocabulary),
+        _ => Vec::new(),
+    };
+    // Every reading resolves "today" against the same instant.
+    let now = Utc::now();
+
+    // A corrected word no rule used was not meant as that keyword; report it as typed.
+    let revert = |ignored_tokens: &mut Vec<QueryToken>| {
+        for token in ignored_tokens {
+            if let Some(correction) = corrections.iter().find(|c| c.span == token.span) {
+                token.text = correction.text.clone();
+            }
+        }
+    };
+
+    // A translated query reports the words as typed rather than their English.
+    let untranslate = |tokens: &[QueryToken], reported: &[QueryToken]| match language {
+        Language::English => tokens.to_vec(),
+        _ => as_typed(tokens, &typed, reported),
+    };
+
+    let mut reading = read(query, &tokens, options, vocabulary, now, None).map_err(|mut e| {
+        revert(&mut e.ignored_tokens);
+        e.consumed_tokens = untranslate(&e.consumed_tokens, &[]);
+        e.ignored_tokens = untranslate(&e.ignored_tokens, &e.consumed_tokens);
+        e
+    })?;
+    revert(&mut reading.ignored_tokens);
+
+    let corrections = corrections
+        .into_iter()
+        .filter(|correction| {
+            reading
+                .consumed_tokens
+                .iter()
+                .any(|token| token.span == correction.span)
+        })
+        .collect::<Vec<Correction>>();
+
+    let chars = query.chars().collect::<Vec<char>>();
+    let phrase = |span: TextSpan| chars[span.start..span.end].iter().collect::<String>();
+    let span = |range: &Range<usize>| union(&tokens[range.clone()]);
+
+    let filters = reading
+        .matches
+        .iter()
+        .map(|rule| {
+            let parsed_filters = read(
+                query,
+                &tokens[rule.tokens.clone()],
+                options,
+                vocabulary,
+                now,
+                None,
+            )
+            .map_or_else(|_| serde_json::json!({}), |phrase| phrase.filters.applied());
+
+            InterpretedFilter {
+                text: phrase(span(&rule.tokens)),
+                span: span(&rule.tokens),
+                parsed_filters,
+                confidence: round_confidence(
+                    rule.ambiguities.iter().map(|a| a.confidence).product(),
+                ),
+                ambiguities: rule.ambiguities.iter().map(|a| a.reason.clone()).collect(),
+            }
+        })
+        .collect::<Vec<InterpretedFilter>>();
+
+    let unused_tokens = reading
+        .ignored_tokens
+        .iter()
+        .filter(|token| {
+            !vocabulary.filler.contains(&token.text) && !language.is_filler(&token.text)
+        })
+        .cloned()
+        .collect::<Vec<QueryToken>>();
+
+    let confidence = filters
+        .iter()
+        .map(|filter| filter.confidence)
+        .product::<f32>()
+        * UNUSED_TOKEN_CONFIDENCE.powi(unused_tokens.len() as i32)
+        * CORRECTION_CONFIDENCE.powi(corrections.len() as i32);
+
+    let mut alternatives = reading
+        .matches
+        .iter()
+        .flat_map(|rule| rule.ambiguities.iter().map(move |a| (rule, a)))
+        .filter_map(|(rule, ambiguity)| {
+            let alternative = read(
+                query,
+                &tokens,
+                options,
+                vocabulary,
+                now,
+                Some((rule.start, ambiguity.kind)),
+            )
+            .ok()?;
+
+            Some(AlternativeInterpretation {
+                reason: ambiguity.reason.clone(),
+                span: span(&rule.tokens),
+                parsed_filters: alternative.filters.applied(),
+                confidence: round_confidence(
+                    confidence / ambiguity.confidence * (1.0 - ambiguity.confidence),
+                ),
+            })
+        })
+        .collect::<Vec<AlternativeInterpretation>>();
+    alternatives.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+    let consumed_tokens = untranslate(&reading.consumed_tokens, &[]);
+
+    Ok(ParsedQuery {
+        original: query.to_string(),
+        language,
+        filters: reading.filters,
+        confidence: round_confidence(confidence),
+        interpreted_filters: filters,
+        alternatives,
+        ignored_tokens: untranslate(&reading.ignored_tokens, &consumed_tokens),
+        unused_tokens: untranslate(&unused_tokens, &consumed_tokens),
+        consumed_tokens,
+        corrections,
+    })
+}
+
+/// The typed tokens that `tokens` of a translated query came from, in query order: a
+/// phrase such as "plus longues que" is reported word by word. Words already `reported`
+/// are left out, as a word can be moved into a phrase that was read.
+fn as_typed(tokens: &[QueryToken], typed: &[Token], reported: &[QueryToken]) -> Vec<QueryToken> {
+    let within =
+        |span: TextSpan, outer: TextSpan| outer.start <= span.start && span.end <= outer.end;
+
+    typed
+        .iter()
+        .filter(|token| tokens.iter().any(|other| within(token.span, other.span)))
+        .filter(|token| !reported.iter().any(|other| other.span == token.span))
+        .map(|token| QueryToken {
+            text: token.text.clone(),
+            span: token.span,
+        })
+        .collect()
+}
+
+/// Two decimal places are all a client needs to compare readings.
+fn round_confidence(confidence: f32) -> f32 {
+    (confidence * 100.0).round() / 100.0
+}
+
+/// One way of reading the query's tokens.
+struct Reading {
+    filters: StringFilters,
+    matches: Vec<RuleMatch>,
+    consumed_tokens: Vec<QueryToken>,
+    ignored_tokens: Vec<QueryToken>,
+}
+
+/// Reads `tokens` into filters, taking the other side of `alternative` when given.
+fn read(
+    query: &str,
+    tokens: &[Token],
+    options: &NlpOptions,
+    vocabulary: &Vocabulary,
+    now: DateTime<Utc>,
+    alternative: Option<(usize, AmbiguityKind)>,
+) -> Result<Reading, NlpError> {
+    let mut parser = Parser::new(query, tokens, options, vocabulary, now, alternative);
+    let parsed = parser.parse();
+
+    let (consumed_tokens, ignored_tokens) = split_tokens(tokens, parser.consumed());
+    let error = |reason: String, span: Option<TextSpan>| NlpError {
+        reason,
+        span,
+        consumed_tokens: consumed_tokens.clone(),
+        ignored_tokens: ignored_tokens.clone(),
+    };
+
+    let mut clauses = parsed.map_err(|e| error(e.reason, Some(e.span)))?;
+
+    if clauses.iter().all(Clause::is_empty) {
+        let sorted = clauses
+            .iter()
+            .any(|clause| clause.filters.sort.is_some() || clause.filters.max_results.is_some());
+        if !sorted {
+            return Err(error(
+                "Unable to parse any valid filters".to_string(),
+                Some(TextSpan {
+                    start: 0,
+                    end: query.chars().count(),
+                }),
+            ));
+        }
+    } else if clauses.len() > 1
+        && let Some(clause) = clauses.iter().find(|clause| clause.is_empty())
+    {
+        return Err(error(
+            "Unable to parse every 'or' clause".to_string(),
+            Some(clause.span(tokens)),
+        ));
+    }
+
+    for clause in &clauses {
+        clause
+            .filters
+            .validate()
+            .map_err(|reason| error(reason, None))?;
+    }
+
+    // A sort or result limit in any clause applies to the whole query; "most bytes" also
+    // keeps the unit the sort is measured in.
+    let sort = clauses.iter().find_map(|clause| {
+        clause
+            .filters
+            .sort
+            .map(|sort| (sort, clause.filters.order, clause.filters.length_unit))
+    });
+    let max_results = clauses.iter().find_map(|clause| clause.filters.max_results);
+
+    let (mut filters, expression) = if clauses.len() == 1 {
+        let clause = clauses.pop().unwrap();
+        let expression = clause.expressions.into_iter().reduce(and);
+        (clause.filters, expression)
+    } else {
+        let expression = clauses
+            .iter()
+            .map(|clause| {
+                clause
+                    .expression()
+                    .map_err(|reason| error(reason, Some(clause.span(tokens))))
+            })
+            .collect::<Result<Vec<FilterExpression>, NlpError>>()?
+            .into_iter()
+            .reduce(|left, right| FilterExpression::Or(Box::new(left), Box::new(right)));
+        (StringFilters::default(), expression)
+    };
+
+    if let Some((sort, order, length_unit)) = sort {
+        filters.sort = Some(sort);
+        filters.order = order;
+        if sort == SortField::Length {
+            filters.length_unit = filters.length_unit.or(length_unit);
+        }
+    }
+    filters.max_results = max_results;
+    filters.filter = expression.as_ref().map(FilterExpression::to_string);
+    filters.expression = expression;
+
+    Ok(Reading {
+        filters,
+        matches: parser.matches(),
+        consumed_tokens,
+        ignored_tokens,
+    })
+}
+
+fn split_tokens(tokens: &[Token], consumed: &[bool]) -> (Vec<QueryToken>, Vec<QueryToken>) {
+    let (consumed_tokens, ignored_tokens): (Vec<_>, Vec<_>) = tokens
+        .iter()
+        .zip(consumed)
+        .partition(|(_, consumed)| **consumed);
+
+    let query_tokens = |tokens: Vec<(&Token, &bool)>| {
+        tokens
+            .into_iter()
+            .map(|(token, _)| QueryToken {
+                text: token.text.clone(),
+                span: token.span,
+            })
+            .collect()
+    };
+
+    (query_tokens(consumed_tokens), query_tokens(ignored_tokens))
+}
+
+impl Clause {
+    fn starting_at(index: usize) -> Self {
+        Self {
+            tokens: index..index,
+            ..Default::default()
+        }
+    }
+
+    /// Character span of the clause, or of where it was expected when it has no tokens.
+    fn span(&self, tokens: &[Token]) -> TextSpan {
+        let end_of_query = tokens.last().map_or(0, |token| token.span.end);
+
+        match (
+            tokens.get(self.tokens.start),
+            self.tokens.end.checked_sub(1),
+        ) {
+            (Some(_), Some(last)) if last >= self.tokens.start => {
+                union(&tokens[self.tokens.start..=last])
+            }
+            _ => TextSpan {
+                start: end_of_query,
+                end: end_of_query,
+            },
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        let filters = &self.filters;
+
+        self.expressions.is_empty()
+            && filters.is_palindrome.is_none()
+            && filters
+                .numeric_ranges()
+                .iter()
+                .all(|range| range.exact.is_none() && range.min.is_none() && range.max.is_none())
+            && filters.contains_character.is_none()
+            && filters.contains_all.is_none()
+            && filters.contains_any.is_none()
+            && filters.excludes.is_none()
+            && filters.min_char_count.is_none()
+            && filters.max_char_count.is_none()
+            && filters.created_after.is_none()
+            && filters.created_before.is_none()
+    }
+
+    /// The clause as a single expression, so it can be ORed with the other clauses.
+    fn expression(&self) -> Result<FilterExpression, String> {
+        let filters = &self.filters;
+
+        if filters.min_char_count.is_some() || filters.max_char_count.is_some() {
+            return Err("Character counts cannot be combined with 'or'".to_string());
+        }
+
+        let mut conditions = Vec::new();
+
+        if let Some(value) = filters.is_palindrome {
+            conditions.push(FilterExpression::Condition(Condition::Palindrome {
+                mode: filters.palindrome_mode.unwrap_or_default(),
+                value,
+            }));
+        }
+
+        for range in filters.numeric_ranges() {
+            let bounds = [
+                (range.exact, Comparison::Equal),
+                (range.min, Comparison::GreaterOrEqual),
+                (range.max, Comparison::LessOrEqual),
+            ];
+
+            for (value, comparison) in bounds {
+                if let Some(value) = value {
+                    conditions.push(numeric(range.column, comparison, value));
+                }
+            }
+        }
+
+        for characters in [&filters.contains_character, &filters.contains_all] {
+            let characters = character_set(characters.as_deref());
+            if !characters.is_empty() {
+                conditions.push(FilterExpression::Condition(Condition::Contains(characters)));
+            }
+        }
+
+        let any = character_set(filters.contains_any.as_deref())
+            .into_iter()
+            .map(|character| FilterExpression::Condition(Condition::Contains(vec![character])))
+            .reduce(|left, right| FilterExpression::Or(Box::new(left), Box::new(right)));
+        conditions.extend(any);
+
+        conditions.extend(character_set(filters.excludes.as_deref()).into_iter().map(
+            |character| {
+                not(FilterExpression::Condition(Condition::Contains(vec![
+                    character,
+                ])))
+            },
+        ));
+
+        let created = [
+            (filters.created_after, Comparison::GreaterOrEqual),
+            (filters.created_before, Comparison::Less),
+        ];
+        for (value, comparison) in created {
+            conditions.extend(value.map(|value| created_at(comparison, value)));
+        }
+
+        conditions.extend(self.expressions.iter().cloned());
+
+        conditions
+            .into_iter()
+            .reduce(and)
+            .ok_or_else(|| "Unable to parse every 'or' clause".to_string())
+    }
+}
+
+fn numeric(column: &'static str, comparison: Comparison, value: i32) -> FilterExpression {
+    FilterExpression::Condition(Condition::Numeric {
+        column,
+        comparison,
+        value,
+    })
+}
+
+fn ratio(column: &'static str, comparison: Comparison, value: f64) -> FilterExpression {
+    FilterExpression::Condition(Condition::Ratio {
+        column,
+        comparison,
+        value,
+    })
+}
+
+fn created_at(comparison: Comparison, value: Timestamp) -> FilterExpression {
+    FilterExpression::Condition(Condition::CreatedAt { comparison, value })
+}
+
+fn not(expression: FilterExpression) -> FilterExpression {
+    FilterExpression::Not(Box::new(expression))
+}
+
+fn and(left: FilterExpression, right: FilterExpression) -> FilterExpression {
+    FilterExpression::And(Box::new(left), Box::new(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::{Language, NlpOptions, ParsedQuery, Vocabulary, parse_natural_language};
+    use crate::models::nlp::QueryToken;
+
+    fn parse(query: &str) -> ParsedQuery {
+        parse_natural_language(query, &NlpOptions::default(), &Vocabulary::default())
+            .unwrap_or_else(|e| panic!("'{}' does not parse: {}", query, e.reason))
+    }
+
+    fn filters(query: &str) -> Value {
+        parse(query).filters.applied()
+    }
+
+    /// Each token's text and where it starts.
+    fn words(tokens: &[QueryToken]) -> Vec<(&str, usize)> {
+        tokens
+            .iter()
+            .map(|token| (token.text.as_str(), token.span.start))
+            .collect()
+    }
+
+    /// The reason and character span of the error `query` is rejected with.
+    fn error(query: &str) -> (String, Option<(usize, usize)>) {
+        let error = parse_natural_language(query, &NlpOptions::default(), &Vocabulary::default())
+            .err()
+            .unwrap_or_else(|| panic!("'{}' parses", query));
+
+        (error.reason, error.span.map(|span| (span.start, span.end)))
+    }
+
+    #[test]
+    fn character_classes() {
+        for (query, expected) in [
+            ("containing digits", json!({ "min_digit_count": 1 })),
+            ("with a digit", json!({ "min_digit_count": 1 })),
+            ("with no emoji", json!({ "max_emoji_count": 0 })),
+            ("not containing digits", json!({ "max_digit_count": 0 })),
+            (
+                "without digits or spaces",
+                json!({ "max_digit_count": 0, "max_whitespace_count": 0 }),
+            ),
+            (
+                "with digits and symbols",
+                json!({ "min_digit_count": 1, "min_symbol_count": 1 }),
+            ),
+            ("with 2 uppercase letters", json!({ "uppercase_count": 2 })),
+            ("3 digits", json!({ "digit_count": 3 })),
+            ("more than 2 digits", json!({ "min_digit_count": 3 })),
+            ("with at least 2 digits", json!({ "min_digit_count": 2 })),
+            ("fewer than 3 spaces", json!({ "max_whitespace_count": 2 })),
+            (
+                "between 1 and 3 symbols",
+                json!({ "min_symbol_count": 1, "max_symbol_count": 3 }),
+            ),
+            (
+                "containing punctuation marks",
+                json!({ "min_punctuation_count": 1 }),
+            ),
+            (
+                "uppercase strings",
+                json!({ "min_uppercase_count": 1, "max_lowercase_count": 0 }),
+            ),
+            (
+                "all-lowercase strings",
+                json!({ "min_lowercase_count": 1, "max_uppercase_count": 0 }),
+            ),
+        ] {
+            assert_eq!(filters(query), expected, "{}", query);
+        }
+    }
+
+    #[test]
+    fn number_words() {
+        for (query, expected) in [
+            (
+                "longer than one hundred characters",
+                json!({ "min_length": 101 }),
+            ),
+            (
+                "longer than a thousand characters",
+                json!({ "min_length": 1001 }),
+            ),
+            (
+                "shorter than nine hundred million characters",
+                json!({ "max_length": 899_999_999 }),
+            ),
+            (
+                "with 3 thousand characters",
+                json!({ "min_length": 3000, "max_length": 3000 }),
+            ),
+            (
+                "with 12 hundred characters",
+                json!({ "min_length": 1200, "max_length": 1200 }),
+            ),
+            (
+                "between one and two hundred characters",
+                json!({ "min_length": 1, "max_length": 200 }),
+            ),
+        ] {
+            assert_eq!(filters(query), expected, "{}", query);
+        }
+    }
+
+    #[test]
+    fn malformed_number_words() {
+        for (query, reason, span) in [
+            (
+                "longer than one hundred thousand million characters",
+                "'one hundred thousand million' is too large",
+                (12, 40),
+            ),
+            (
+                "with nine hundred ninety nine thousand million characters",
+                "'nine hundred ninety nine thousand million' is too large",
+                (5, 46),
+            ),
+            (
+                "longer than twenty five hundred million characters",
+                "'twenty five hundred million' is too large",
+                (12, 39),
+            ),
+            (
+                "longer than one thousand thousand characters",
+                "'one thousand thousand' is not a valid number",
+                (12, 33),
+            ),
+            (
+                "longer than five twenty characters",
+                "'five twenty' is not a valid number",
+                (12, 23),
+            ),
+            (
+                "with 3 five characters",
+                "'3 five' is not a valid number",
+                (5, 11),
+            ),
+            (
+                "the five twenty longest strings",
+                "'five twenty' is not a valid number",
+                (4, 15),
+            ),
+        ] {
+            assert_eq!(error(query), (reason.to_string(), Some(span)), "{}", query);
+        }
+    }
+
+    #[test]
+    fn negations() {
+        for query in [
+            "strings that don't contain z",
+            "strings that doesn't contain z",
+            "strings that dont contain z",
+            "strings that doesnt contain z",
+            "strings that aren't containing z",
+        ] {
+            let parsed = parse(query);
+
+            assert_eq!(
+                parsed.filters.applied(),
+                json!({ "excludes": "z" }),
+                "{}",
+                query
+            );
+            assert!(parsed.corrections.is_empty(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn sort_limits() {
+        for (query, expected) in [
+            (
+                "the 5 longest strings",
+                json!({ "sort": "length", "order": "desc", "max_results": 5 }),
+            ),
+            (
+                "most recent 10",
+                json!({ "sort": "created_at", "order": "desc", "max_results": 10 }),
+            ),
+            ("top 3 strings", json!({ "max_results": 3 })),
+        ] {
+            assert_eq!(filters(query), expected, "{}", query);
+        }
+
+        for (query, span) in [
+            ("the 99999999999 longest strings", (4, 15)),
+            ("top 99999999999 strings", (4, 15)),
+            ("most recent 99999999999", (12, 23)),
+            ("longer than 99999999999 characters", (12, 23)),
+        ] {
+            assert_eq!(
+                error(query),
+                ("'99999999999' is too large".to_string(), Some(span)),
+                "{}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn languages() {
+        for (query, language, expected) in [
+            (
+                "palindromes",
+                Language::English,
+                json!({ "is_palindrome": true }),
+            ),
+            (
+                "strings longer than 5",
+                Language::English,
+                json!({ "min_length": 6 }),
+            ),
+            (
+                "plus long que 5",
+                Language::French,
+                json!({ "min_length": 6 }),
+            ),
+            (
+                "chaînes plus longues que 5 caractères",
+                Language::French,
+                json!({ "min_length": 6 }),
+            ),
+            (
+                "palindromes plus longs que 3",
+                Language::French,
+                json!({ "is_palindrome": true, "min_length": 4 }),
+            ),
+            (
+                "plus court que vingt et un caractères",
+                Language::French,
+                json!({ "max_length": 20 }),
+            ),
+            (
+                "le palindrome le plus long",
+                Language::French,
+                json!({ "is_palindrome": true, "sort": "length", "order": "desc", "max_results": 1 }),
+            ),
+            (
+                "palíndromos",
+                Language::Spanish,
+                json!({ "is_palindrome": true }),
+            ),
+            (
+                "más largas que 5",
+                Language::Spanish,
+                json!({ "min_length": 6 }),
+            ),
+            (
+                "cadenas con más de 3 vocales",
+                Language::Spanish,
+                json!({ "min_vowel_count": 4 }),
+            ),
+            (
+                "más cortas que treinta y dos",
+                Language::Spanish,
+                json!({ "max_length": 31 }),
+            ),
+            (
+                "los 3 palíndromos más largos",
+                Language::Spanish,
+                json!({ "is_palindrome": true, "sort": "length", "order": "desc", "max_results": 3 }),
+            ),
+        ] {
+            let parsed = parse(query);
+
+            assert_eq!(parsed.language, language, "{}", query);
+            assert_eq!(parsed.filters.applied(), expected, "{}", query);
+        }
+
+        // A requested language is not second-guessed.
+        let options = NlpOptions {
+            language: Some(Language::French),
+            ..NlpOptions::default()
+        };
+        let parsed = parse_natural_language("palindromes", &options, &Vocabulary::default())
+            .unwrap_or_else(|e| panic!("'palindromes' does not parse: {}", e.reason));
+        assert_eq!(parsed.language, Language::French);
+        assert_eq!(parsed.filters.applied(), json!({ "is_palindrome": true }));
+    }
+
+    #[test]
+    fn french_and_spanish_negations() {
+        for (query, expected) in [
+            (
+                "les chaînes qui ne contiennent pas de a",
+                json!({ "excludes": "a" }),
+            ),
+            ("chaînes ne contenant pas de z", json!({ "excludes": "z" })),
+            (
+                "ne sont pas des palindromes",
+                json!({ "is_palindrome": false }),
+            ),
+            ("pas de palindromes", json!({ "is_palindrome": false })),
+            ("chaînes sans a", json!({ "excludes": "a" })),
+            ("cadenas sin a ni e", json!({ "excludes": "ae" })),
+            (
+                "palíndromos sin a",
+                json!({ "is_palindrome": true, "excludes": "a" }),
+            ),
+            (
+                "palabras que no son palíndromos",
+                json!({ "is_palindrome": false }),
+            ),
+        ] {
+            assert_eq!(filters(query), expected, "{}", query);
+        }
+    }
+
+    #[test]
+    fn reports_words_as_typed() {
+        let parsed = parse("chaînes plus longues que 5 caractères");
+        assert_eq!(
+            words(&parsed.consumed_tokens),
+            [
+                ("plus", 8),
+                ("longues", 13),
+                ("que", 21),
+                ("5", 25),
+                ("caractères", 27)
+            ]
+        );
+        assert_eq!(words(&parsed.ignored_tokens), [("chaînes", 0)]);
+
+        // "pas" is moved next to "ne" to be read, but is reported where it was typed.
+        let parsed = parse("les chaînes qui ne contiennent pas de a");
+        assert_eq!(
+            words(&parsed.consumed_tokens),
+            [("contiennent", 19), ("pas", 31), ("a", 38)]
+        );
+        assert_eq!(
+            words(&parsed.ignored_tokens),
+            [
+                ("les", 0),
+                ("chaînes", 4),
+                ("qui", 12),
+                ("ne", 16),
+                ("de", 35)
+            ]
+        );
+
+        let parsed = parse("cadenas sin a ni e");
+        assert_eq!(
+            words(&parsed.consumed_tokens),
+            [("sin", 8), ("a", 12), ("ni", 14), ("e", 17)]
+        );
+        assert_eq!(words(&parsed.unused_tokens), []);
+    }
+}
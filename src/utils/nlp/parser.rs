@@ -0,0 +1,2031 @@
+//! Recursive-descent parser for natural language queries.
+//!
+//! ```text
+//! query       := clause ("or" clause)*
+//! clause      := ([negation] filter | sort | IGNORED)*
+//! negation    := "not" | "non" | "isn't" | "aren't" | "don't" | "doesn't" | "isnt" | "arent" | "dont"
+//!              | "doesnt"
+//! filter      := palindrome | comparison | balance | adjective | exact | between
+//!              | approximate | created | word_count | class | letter_case | contains | ordinal
+//! palindrome  := [mode] ("palindrome" | "palindromes" | "palindromic")
+//!              | "non-palindrome" | "non-palindromes" | "non-palindromic" | "nonpalindromic"
+//! mode        := "strict" | "loose" | "alphanumeric" | "normalised" | "normalized" | "unicode"
+//! sort        := ["top"] [NUMBER] superlative [NUMBER] | "top" NUMBER
+//! superlative := "longest" | "shortest" | "newest" | "latest" | "recent" | "oldest"
+//!              | "earliest" | "alphabetical" | "alphabetically" | ("most" | "least") "recent"
+//!              | ("most" | "least" | "fewest") (measure | "unique" ("characters" | "letters"))
+//! comparison  := (["no"] comparative "than" | "at" "least" | "at" "most") NUMBER [measure]
+//! comparative := "longer" | "more" | "greater" | "bigger" | "larger" | "shorter" | "less"
+//!              | "fewer" | "smaller"
+//! balance     := ["no"] ("more" | "greater" | "less" | "fewer") letters "than" letters
+//! letters     := "vowel" | "vowels" | "consonant" | "consonants"
+//! adjective   := SHORT | LONG
+//! exact       := ("exactly" | "equals") NUMBER [measure] | NUMBER length_unit
+//! length_unit := "byte" | "bytes" | "codepoint" | "codepoints" | "code" ("point" | "points")
+//!              | "grapheme" | "graphemes" | "character" | "characters" | "char" | "chars"
+//!              | "letter" | "letters"
+//! between     := "between" NUMBER "and" NUMBER [measure]
+//! approximate := ("about" | "around" | "approximately" | "approx" | "roughly" | "circa")
+//!                NUMBER [measure]
+//! created     := [("added" | "created" | "from" | "during") ["the"]]
+//!                (("since" | "after") DATE [["and"] "before" DATE] | "before" DATE
+//!                | "on" DATE | ("last" | "past") [NUMBER] period | DATE)
+//! period      := "hour" | "hours" | "day" | "days" | "week" | "weeks" | "month" | "months"
+//!              | "year" | "years"
+//! measure     := length_unit | "word" | "words" | class_noun
+//! word_count  := NUMBER ("word" | "words") | NUMBER "-word" | "single" ["word" | "words"]
+//! class       := lead ["no" | "a" | "an" | "any" | "some"] class_noun
+//!                ((("," ["and" | "or"]) | "and" | "or") class_noun)* | [lead] NUMBER class_noun
+//! class_noun  := "digit" | "digits" | "space" | "spaces" | "symbol" | "symbols" | "emoji"
+//!              | "emojis" | letters | ("uppercase" | "upper-case" | "capital" | "capitals" | "lowercase"
+//!              | "lower-case" | "punctuation" | "whitespace")
+//!                [("letter" | "letters" | "character" | "characters" | "mark" | "marks")]
+//! letter_case := "all-caps" | "allcaps" | "all" "caps" | "all-uppercase" | "all-lowercase"
+//!              | "uppercase" | "upper-case" | "lowercase" | "lower-case"
+//! contains    := lead ["no"] characters [count]
+//! lead        := "contain" | "contains" | "containing" | "with" | "without" | "exclude"
+//!              | "excludes" | "excluding"
+//! characters  := character (("," ["and" | "or"] | "and" | "or") character)*
+//! character   := [("the" | "a")] ("letter" | "letters" | "character" | "characters") CHAR
+//!              | ["the"] ordinal | CHAR
+//! ordinal     := ORDINAL ("vowel" | "consonant" | "letter" | "alphabet")
+//! count       := ["at" "least" | "at" "most" | ["no"] ("more" | "fewer" | "less") "than"
+//!              | "exactly"] (NUMBER ("time" | "times") | "once" | "twice" | "thrice")
+//! ```
+//!
+//! `CHAR` is any single-character word or quoted token. `NUMBER` is digits or number words
+//! ("five", "twenty one", "twenty-one", "a hundred", "3 thousand"); "and" never joins number
+//! words, so "between one hundred and two hundred" stays two numbers, and a number word that
+//! cannot continue a number ("five twenty", "thousand million") is a syntax error. `ORDINAL` is a named ordinal up
+//! to "ninetieth" or "hundredth", a compound ("twenty-first", "twenty first") or a numeric
+//! ordinal such as "12th". `DATE` is "today", "yesterday", ("this" | "last") period, a
+//! `YYYY-MM-DD` date or an RFC 3339 timestamp, all in UTC: "last week" is the previous
+//! calendar week, "last 2 weeks" and "past week" end now. A number after a superlative is
+//! a result limit unless a measure follows it ("longest 3 word strings"); with no number,
+//! a singular noun after it ("shortest string") limits the results to one. A negation
+//! applies to the filter right after it. Tokens outside every rule ("strings", "all",
+//! "that") are ignored; a rule that starts matching but cannot finish ("longer than x") is
+//! a syntax error. "uppercase" and "lowercase" on their own describe the whole string
+//! ("uppercase strings" are all-caps); with a lead or a number they count letters.
+//!
+//! Quoted keywords are the defaults; the negation, palindrome, comparison, exact,
+//! approximate, contains and superlative words, and the `SHORT` / `LONG` adjectives (none
+//! by default), come from the [`Vocabulary`] and may span several words.
+
+use std::ops::Range;
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
+
+use crate::{
+    models::{
+        expression::{Comparison, Condition, FilterExpression},
+        filters::{CharacterClass, LengthUnit, PalindromeMode, SortField, SortOrder, Timestamp},
+        nlp::TextSpan,
+    },
+    utils::nlp::{
+        Clause, NlpOptions, and, created_at,
+        lexer::{Token, TokenKind, union},
+        not, numeric, ratio,
+        vocabulary::Vocabulary,
+    },
+};
+
+/// Words the grammar matches itself rather than taking from the vocabulary; number words
+/// and ordinals are left to `is_number_word`.
+pub const KEYWORDS: [&str; 92] = [
+    "than",
+    "and",
+    "or",
+    "no",
+    "at",
+    "on",
+    "between",
+    "most",
+    "least",
+    "fewest",
+    "unique",
+    "top",
+    "single",
+    "byte",
+    "bytes",
+    "codepoint",
+    "codepoints",
+    "code",
+    "point",
+    "points",
+    "grapheme",
+    "graphemes",
+    "character",
+    "characters",
+    "char",
+    "chars",
+    "letter",
+    "letters",
+    "word",
+    "words",
+    "digit",
+    "digits",
+    "uppercase",
+    "upper-case",
+    "lowercase",
+    "lower-case",
+    "capital",
+    "capitals",
+    "punctuation",
+    "mark",
+    "marks",
+    "whitespace",
+    "space",
+    "spaces",
+    "symbol",
+    "symbols",
+    "emoji",
+    "emojis",
+    "all-caps",
+    "allcaps",
+    "all-uppercase",
+    "all-lowercase",
+    "caps",
+    "some",
+    "vowel",
+    "vowels",
+    "consonant",
+    "consonants",
+    "alphabet",
+    "time",
+    "times",
+    "once",
+    "twice",
+    "thrice",
+    "strict",
+    "loose",
+    "alphanumeric",
+    "normalised",
+    "normalized",
+    "unicode",
+    "added",
+    "created",
+    "from",
+    "during",
+    "since",
+    "after",
+    "before",
+    "today",
+    "yesterday",
+    "this",
+    "last",
+    "past",
+    "hour",
+    "hours",
+    "day",
+    "days",
+    "week",
+    "weeks",
+    "month",
+    "months",
+    "year",
+    "years",
+];
+
+/// A rule that started matching but could not be completed.
+pub struct SyntaxError {
+    pub reason: String,
+    pub span: TextSpan,
+}
+
+/// What a number in a comparison, "exactly" or "between" phrase measures.
+#[derive(Clone, Copy)]
+enum Measure {
+    /// Length, in `length_unit` when one was named.
+    Length(Option<LengthUnit>),
+    Words,
+    /// Characters of one class: "3 digits".
+    Class(CharacterClass),
+}
+
+impl Measure {
+    fn column(&self) -> &'static str {
+        match self {
+            Measure::Length(unit) => unit.unwrap_or_default().column(),
+            Measure::Words => "word_count",
+            Measure::Class(class) => class.column(),
+        }
+    }
+}
+
+/// A point where the query could be read more than one way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbiguityKind {
+    /// A number with no unit, read as characters: "longer than 5".
+    UnstatedMeasure,
+    /// "about N", read as a range around N.
+    Approximate,
+    /// "with a", read as the letter a.
+    Article,
+    /// "shortest string", read as asking for one result.
+    SingularSuperlative,
+}
+
+#[derive(Debug, Clone)]
+pub struct Ambiguity {
+    pub kind: AmbiguityKind,
+    pub reason: String,
+    /// How likely the chosen reading is, 0 to 1.
+    pub confidence: f32,
+}
+
+/// Tokens one rule turned into a filter or sort, and what it was unsure about.
+#[derive(Debug, Clone)]
+pub struct RuleMatch {
+    /// Indices of the matched tokens, including a leading negation.
+    pub tokens: Range<usize>,
+    /// Index the rule itself started at, identifying its ambiguities.
+    pub start: usize,
+    pub ambiguities: Vec<Ambiguity>,
+}
+
+pub struct Parser<'a> {
+    /// The query as typed, which `tokens` span.
+    query: &'a str,
+    tokens: &'a [Token],
+    options: NlpOptions,
+    vocabulary: &'a Vocabulary,
+    /// What "today" and "the last 3 days" are relative to.
+    now: DateTime<Utc>,
+    /// The ambiguity to read the other way, by the index of the rule that raised it.
+    alternative: Option<(usize, AmbiguityKind)>,
+    index: usize,
+    consumed: Vec<bool>,
+    clauses: Vec<Clause>,
+    matches: Vec<RuleMatch>,
+    /// Ambiguities raised by the rule being tried.
+    ambiguities: Vec<Ambiguity>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(
+        query: &'a str,
+        tokens: &'a [Token],
+        options: &NlpOptions,
+        vocabulary: &'a Vocabulary,
+        now: DateTime<Utc>,
+        alternative: Option<(usize, AmbiguityKind)>,
+    ) -> Self {
+        Self {
+            query,
+            tokens,
+            options: *options,
+            vocabulary,
+            now,
+            alternative,
+            index: 0,
+            consumed: vec![false; tokens.len()],
+            clauses: Vec::new(),
+            matches: Vec::new(),
+            ambiguities: Vec::new(),
+        }
+    }
+
+    /// Whether each token was turned into (part of) a filter.
+    pub fn consumed(&self) -> &[bool] {
+        &self.consumed
+    }
+
+    /// Every rule that matched, in query order.
+    pub fn matches(&mut self) -> Vec<RuleMatch> {
+        std::mem::take(&mut self.matches)
+    }
+
+    pub fn parse(&mut self) -> Result<Vec<Clause>, SyntaxError> {
+        self.check_numbers()?;
+
+        let mut negation = None;
+        self.clauses.push(Clause::starting_at(0));
+
+        while self.index < self.tokens.len() {
+            if self.is_word(0, &["or"]) {
+                self.close_clause();
+                self.consume(1);
+                self.clauses.push(Clause::starting_at(self.index));
+                negation = None;
+                continue;
+            }
+
+            if let Some(length) = self.phrase(0, &self.vocabulary.negation) {
+                negation = Some(self.index..self.index + length);
+                self.index += length;
+                continue;
+            }
+
+            let start = self.index;
+            self.ambiguities.clear();
+
+            if self.sort() {
+                self.record_match(start, start);
+                continue;
+            }
+
+            let negate = negation.is_some();
+            let matched = self.palindrome(negate)
+                || self.comparison(negate)?
+                || self.vowel_balance(negate)
+                || self.length_adjective(negate)
+                || self.exact(negate)?
+                || self.between(negate)?
+                || self.approximate(negate)?
+                || self.created(negate)?
+                || self.word_count(negate)
+                || self.character_class(negate)
+                || self.letter_case(negate)
+                || self.contains(negate)?
+                || self.ordinal(negate)?;
+
+            if matched {
+                let negation = negation.take();
+                let first = negation.as_ref().map_or(start, |negation| negation.start);
+                for position in negation.into_iter().flatten() {
+                    self.consumed[position] = true;
+                }
+                self.record_match(first, start);
+            } else {
+                self.index += 1;
+            }
+        }
+
+        self.close_clause();
+
+        Ok(std::mem::take(&mut self.clauses))
+    }
+
+    fn record_match(&mut self, first: usize, start: usize) {
+        self.matches.push(RuleMatch {
+            tokens: first..self.index,
+            start,
+            ambiguities: std::mem::take(&mut self.ambiguities),
+        });
+    }
+
+    /// Notes that the rule starting at the current token is ambiguous, returning whether
+    /// to take the alternative reading instead.
+    fn ambiguous(&mut self, kind: AmbiguityKind, reason: String, confidence: f32) -> bool {
+        self.ambiguities.push(Ambiguity {
+            kind,
+            reason,
+            confidence,
+        });
+
+        self.alternative == Some((self.index, kind))
+    }
+
+    /// A number with no unit counts characters; it could count words.
+    fn unstated_measure(
+        &mut self,
+        measure: Measure,
+        measure_len: usize,
+        confidence: f32,
+    ) -> Measure {
+        if measure_len > 0 {
+            return measure;
+        }
+
+        let reason = "No unit given, so the number was read as characters rather than words";
+        if self.ambiguous(
+            AmbiguityKind::UnstatedMeasure,
+            reason.to_string(),
+            confidence,
+        ) {
+            Measure::Words
+        } else {
+            measure
+        }
+    }
+
+    fn clause(&mut self) -> &mut Clause {
+        self.clauses.last_mut().unwrap()
+    }
+
+    fn close_clause(&mut self) {
+        let end = self.index;
+        self.clause().tokens.end = end;
+    }
+
+    fn token(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens.get(self.index + offset)
+    }
+
+    fn word(&self, offset: usize) -> Option<&'a str> {
+        self.token(offset)
+            .filter(|token| token.kind == TokenKind::Word)
+            .map(|token| token.text.as_str())
+    }
+
+    fn is_word(&self, offset: usize, words: &[&str]) -> bool {
+        self.word(offset).is_some_and(|word| words.contains(&word))
+    }
+
+    /// How many tokens the longest of `phrases` starting at `offset` takes; a vocabulary
+    /// phrase may be several words ("mirror words").
+    fn phrase(&self, offset: usize, phrases: &[String]) -> Option<usize> {
+        phrases
+            .iter()
+            .filter_map(|phrase| {
+                let mut length = 0;
+                for word in phrase.split(' ') {
+                    if self.word(offset + length)? != word {
+                        return None;
+                    }
+                    length += 1;
+                }
+                Some(length)
+            })
+            .max()
+    }
+
+    /// The query text of the tokens at `offsets`, e.g. a multi-word phrase for an error.
+    fn text(&self, offsets: Range<usize>) -> String {
+        offsets
+            .filter_map(|offset| self.token(offset))
+            .map(|token| token.text.as_str())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// Reads a number written in digits ("21") or words ("twenty one", "twenty-one",
+    /// "a hundred", "3 thousand"), returning its value and how many tokens it took.
+    /// Malformed numbers read as no number; `parse` has already rejected them.
+    fn cardinal(&self, offset: usize) -> Option<(i32, usize)> {
+        self.read_cardinal(offset).ok().flatten()
+    }
+
+    /// `cardinal`, or an error covering the number when it is out of range or a number word
+    /// after it cannot continue it: "one hundred thousand million" is not 100000 followed
+    /// by a stray "million".
+    fn read_cardinal(&self, offset: usize) -> Result<Option<(i32, usize)>, SyntaxError> {
+        let mut cardinal = Cardinal::default();
+        let mut position = offset;
+
+        if let Some(&TokenKind::Number(number)) = self.token(offset).map(|token| &token.kind) {
+            // "3 thousand" continues with number words; "3 five" does not.
+            if !self.word(offset + 1).is_some_and(is_number_phrase_word) {
+                return Ok(Some((number, 1)));
+            }
+            cardinal.current = number.into();
+            cardinal.last = Some(NumberWord::Unit(number.clamp(0, 1)));
+            cardinal.words = 1;
+            position += 1;
+        }
+
+        // "a"/"an" only counts as one before a scale: "a hundred", not "a word".
+        if self.is_word(position, &["a", "an"])
+            && self.word(position + 1).is_some_and(|word| {
+                matches!(
+                    number_word(word),
+                    Some(NumberWord::Hundred | NumberWord::Scale(_))
+                )
+            })
+        {
+            cardinal.current = 1;
+            cardinal.last = Some(NumberWord::Unit(1));
+            position += 1;
+        }
+
+        while let Some(word) = self.word(position) {
+            let mut next = cardinal;
+            if word.split('-').all(|part| next.push(part)) {
+                cardinal = next;
+                position += 1;
+                continue;
+            }
+
+            if cardinal.words > 0 && is_number_phrase_word(word) {
+                let mut end = position + 1;
+                while self.word(end).is_some_and(is_number_phrase_word) {
+                    end += 1;
+                }
+                let too_large = match number_word(word) {
+                    Some(NumberWord::Scale(scale)) => {
+                        (cardinal.total + cardinal.current) * i64::from(scale) > i64::from(i32::MAX)
+                    }
+                    _ => false,
+                };
+
+                return Err(self.malformed_number(offset..end, too_large));
+            }
+            break;
+        }
+
+        if cardinal.words == 0 {
+            // Digits too long for a number are lexed as a word.
+            return match self.word(offset) {
+                Some(word) if word.chars().all(|c| c.is_ascii_digit()) => {
+                    Err(self.malformed_number(offset..offset + 1, true))
+                }
+                _ => Ok(None),
+            };
+        }
+
+        match cardinal.value() {
+            Some(value) => Ok(Some((value, position - offset))),
+            None => Err(self.malformed_number(offset..position, true)),
+        }
+    }
+
+    fn malformed_number(&self, offsets: Range<usize>, too_large: bool) -> SyntaxError {
+        let reason = if too_large {
+            format!("'{}' is too large", self.quote(offsets.clone()))
+        } else {
+            format!("'{}' is not a valid number", self.quote(offsets.clone()))
+        };
+
+        SyntaxError {
+            reason,
+            span: self.span(offsets),
+        }
+    }
+
+    /// Rejects every malformed number before any rule runs, so the rules that only peek
+    /// at numbers never read a truncated one.
+    fn check_numbers(&self) -> Result<(), SyntaxError> {
+        let mut offset = 0;
+        while self.token(offset).is_some() {
+            offset += match self.read_cardinal(offset)? {
+                Some((_, length)) => length,
+                None => 1,
+            };
+        }
+
+        Ok(())
+    }
+
+    fn consume(&mut self, count: usize) {
+        let end = (self.index + count).min(self.tokens.len());
+        for consumed in &mut self.consumed[self.index..end] {
+            *consumed = true;
+        }
+        self.index = end;
+    }
+
+    /// Span of the tokens at `offsets`, or the end of the query when they run past it.
+    fn span(&self, offsets: Range<usize>) -> TextSpan {
+        let end_of_query = self.tokens.last().map_or(0, |token| token.span.end);
+
+        match (self.token(offsets.start), offsets.end.checked_sub(1)) {
+            (Some(_), Some(last)) => {
+                let end = (self.index + last + 1).min(self.tokens.len());
+                let span = union(&self.tokens[self.index + offsets.start..end]);
+                TextSpan {
+                    start: span.start,
+                    end: match self.token(last) {
+                        Some(_) => span.end,
+                        None => end_of_query,
+                    },
+                }
+            }
+            _ => TextSpan {
+                start: end_of_query,
+                end: end_of_query,
+            },
+        }
+    }
+
+    /// The query as typed at `offsets`, for messages: a French query is quoted in French
+    /// although the parser reads its English translation.
+    fn quote(&self, offsets: Range<usize>) -> String {
+        let span = self.span(offsets);
+        self.query
+            .chars()
+            .skip(span.start)
+            .take(span.end - span.start)
+            .collect()
+    }
+
+    fn error(&self, offset: usize, reason: String) -> SyntaxError {
+        SyntaxError {
+            reason,
+            span: self.span(offset..offset + 1),
+        }
+    }
+
+    /// Whether the token before `index` belongs to a number: "five" in "five twenty", or
+    /// "and" after one, since "and" never joins number words.
+    fn after_number(&self) -> bool {
+        let previous = |back: usize| {
+            self.index
+                .checked_sub(back)
+                .and_then(|index| self.tokens.get(index))
+        };
+        let is_number = |token: Option<&Token>| {
+            token.is_some_and(|token| {
+                matches!(token.kind, TokenKind::Number(_)) || is_number_phrase_word(&token.text)
+            })
+        };
+
+        is_number(previous(1))
+            || (previous(1).is_some_and(|token| token.text == "and") && is_number(previous(2)))
+    }
+
+    /// Reads a length or count, which cannot be negative.
+    fn quantity(&self, offset: usize) -> Result<Option<(i32, usize)>, SyntaxError> {
+        match self.cardinal(offset) {
+            Some((value, length)) if value < 0 => Err(SyntaxError {
+                reason: format!(
+                    "Expected a number of 0 or more, found '{}'",
+                    self.quote(offset..offset + length)
+                ),
+                span: self.span(offset..offset + length),
+            }),
+            cardinal => Ok(cardinal),
+        }
+    }
+
+    /// `value + delta`, or an error covering the number at `offsets` when the sum is out of
+    /// range: "longer than 2147483647" has no bound to give.
+    fn shift(&self, value: i32, delta: i32, offsets: Range<usize>) -> Result<i32, SyntaxError> {
+        value.checked_add(delta).ok_or_else(|| SyntaxError {
+            reason: format!("'{}' is too large", self.quote(offsets.clone())),
+            span: self.span(offsets),
+        })
+    }
+
+    /// "longest", "the 5 longest", "most recent 10", "fewest words" or "top 3". Without a
+    /// number, a singular noun after the superlative ("shortest string") asks for one result.
+    fn sort(&mut self) -> bool {
+        let top = self.is_word(0, &["top"]);
+        let (mut count, count_len) = match self.cardinal(usize::from(top)) {
+            Some((count, length)) => (Some(count), usize::from(top) + length),
+            None => (None, 0),
+        };
+
+        let Some((sort, order, unit, sort_len)) = self.superlative(count_len) else {
+            // "top 5 palindromes" limits the results without sorting them.
+            let Some(count) = count.filter(|_| top) else {
+                return false;
+            };
+            self.clause().filters.max_results = Some(count as i64);
+            self.consume(count_len);
+            return true;
+        };
+        let mut length = count_len + sort_len;
+
+        // "most recent 10 strings", but not "longest 3 word strings".
+        if count.is_none()
+            && let Some((trailing, trailing_len)) = self.cardinal(length)
+            && self.measure(length + trailing_len).1 == 0
+            && !self.is_word(length + trailing_len, &["time", "times"])
+        {
+            count = Some(trailing);
+            length += trailing_len;
+        }
+
+        if count.is_none()
+            && let Some(noun) = self.singular_noun(length)
+            && !self.ambiguous(
+                AmbiguityKind::SingularSuperlative,
+                format!(
+                    "'{}' is singular, so only one result is returned; it could mean every match",
+                    self.quote(noun..noun + 1)
+                ),
+                0.7,
+            )
+        {
+            count = Some(1);
+        }
+
+        let filters = &mut self.clause().filters;
+        filters.sort = Some(sort);
+        filters.order = Some(order);
+        if unit.is_some() {
+            filters.length_unit = unit;
+        }
+        if let Some(count) = count {
+            filters.max_results = Some(count as i64);
+        }
+        self.consume(length);
+
+        true
+    }
+
+    /// Reads a superlative at `offset`, returning the sort it asks for, the length unit it
+    /// names ("most bytes") and how many tokens it took.
+    fn superlative(
+        &self,
+        offset: usize,
+    ) -> Option<(SortField, SortOrder, Option<LengthUnit>, usize)> {
+        let vocabulary = self.vocabulary;
+        let sorts = [
+            (&vocabulary.longest, SortField::Length, SortOrder::Desc),
+            (&vocabulary.shortest, SortField::Length, SortOrder::Asc),
+            (&vocabulary.newest, SortField::CreatedAt, SortOrder::Desc),
+            (&vocabulary.oldest, SortField::CreatedAt, SortOrder::Asc),
+            (&vocabulary.alphabetical, SortField::Value, SortOrder::Asc),
+        ];
+        for (phrases, sort, order) in sorts {
+            if let Some(length) = self.phrase(offset, phrases) {
+                return Some((sort, order, None, length));
+            }
+        }
+
+        match self.word(offset)? {
+            "most" | "least" | "fewest" => {
+                let order = match self.word(offset)? {
+                    "most" => SortOrder::Desc,
+                    _ => SortOrder::Asc,
+                };
+
+                if self.is_word(offset + 1, &["recent"]) {
+                    return Some((SortField::CreatedAt, order, None, 2));
+                }
+                if self.is_word(offset + 1, &["unique"])
+                    && self.is_word(
+                        offset + 2,
+                        &["character", "characters", "letter", "letters"],
+                    )
+                {
+                    return Some((SortField::UniqueCharacters, order, None, 3));
+                }
+
+                match self.measure(offset + 1) {
+                    (_, 0) => None,
+                    (Measure::Words, length) => {
+                        Some((SortField::WordCount, order, None, length + 1))
+                    }
+                    (Measure::Length(unit), length) => {
+                        Some((SortField::Length, order, unit, length + 1))
+                    }
+                    (Measure::Class(_), _) => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Where "string" or "palindrome" is at `offset`, skipping a palindrome mode.
+    fn singular_noun(&self, offset: usize) -> Option<usize> {
+        let offset = offset
+            + usize::from(self.is_word(
+                offset,
+                &[
+                    "strict",
+                    "loose",
+                    "alphanumeric",
+                    "normalised",
+                    "normalized",
+                    "unicode",
+                ],
+            ));
+
+        self.is_word(
+            offset,
+            &["string", "palindrome", "non-palindrome", "entry", "value"],
+        )
+        .then_some(offset)
+    }
+
+    fn palindrome(&mut self, negate: bool) -> bool {
+        let mode = match self.word(0) {
+            Some("strict") => Some(PalindromeMode::Strict),
+            Some("loose" | "alphanumeric") => Some(PalindromeMode::Loose),
+            Some("normalised" | "normalized" | "unicode") => Some(PalindromeMode::Normalised),
+            _ => None,
+        };
+        let offset = usize::from(mode.is_some());
+
+        let (positive, length) = match (
+            self.phrase(offset, &self.vocabulary.palindrome),
+            self.phrase(offset, &self.vocabulary.non_palindrome),
+        ) {
+            (Some(length), _) => (true, length),
+            (None, Some(length)) => (false, length),
+            (None, None) => return false,
+        };
+
+        let filters = &mut self.clause().filters;
+        filters.is_palindrome = Some(positive != negate);
+        if mode.is_some() {
+            filters.palindrome_mode = mode;
+        }
+        self.consume(offset + length);
+
+        true
+    }
+
+    /// "longer than 5" or "more than 3 words", and the inclusive "no more than 5" and "at
+    /// least 5", which read as "not more than 5" and "not fewer than 5".
+    fn comparison(&mut self, negate: bool) -> Result<bool, SyntaxError> {
+        let (greater, confidence, length, inclusive) = match (self.word(0), self.word(1)) {
+            (Some("at"), Some("least")) => (false, 0.6, 2, true),
+            (Some("at"), Some("most")) => (true, 0.6, 2, true),
+            _ => {
+                let no = self.is_word(0, &["no"]);
+                let Some((greater, confidence, length)) = self.comparative(usize::from(no)) else {
+                    return Ok(false);
+                };
+                if !self.is_word(length, &["than"]) {
+                    return Ok(false);
+                }
+                (greater, confidence, length + 1, no)
+            }
+        };
+        let negate = negate != inclusive;
+
+        let Some((value, value_len)) = self.quantity(length)? else {
+            return Err(self.error(
+                length,
+                format!("Expected a number after '{}'", self.quote(0..length)),
+            ));
+        };
+        let number = length..length + value_len;
+
+        let (measure, measure_len) = self.measure(length + value_len);
+        let measure = self.unstated_measure(measure, measure_len, confidence);
+
+        // "not shorter than 5" keeps 5 and up, "shorter than 5" stops at 4.
+        let (min, max) = match (greater, negate) {
+            (true, false) => (Some(self.shift(value, 1, number)?), None),
+            (true, true) => (None, Some(value)),
+            (false, false) => (None, Some(self.shift(value, -1, number)?)),
+            (false, true) => (Some(value), None),
+        };
+        // "shorter than 0" leaves no length to allow.
+        if max.is_some_and(|max| max < 0) {
+            let phrase = 0..length + value_len + measure_len;
+            return Err(SyntaxError {
+                reason: format!("'{}' matches no string", self.quote(phrase.clone())),
+                span: self.span(phrase),
+            });
+        }
+
+        self.apply_bounds(measure, min, max);
+        self.consume(length + value_len + measure_len);
+
+        Ok(true)
+    }
+
+    /// A comparative at `offset` ("longer", "fewer"): whether it asks for more, how sure a
+    /// bare number after it counts characters, and the offset just past it.
+    fn comparative(&self, offset: usize) -> Option<(bool, f32, usize)> {
+        let vocabulary = self.vocabulary;
+        [
+            (&vocabulary.longer, true, 0.9),
+            (&vocabulary.more, true, 0.6),
+            (&vocabulary.shorter, false, 0.9),
+            (&vocabulary.fewer, false, 0.6),
+        ]
+        .into_iter()
+        .find_map(|(phrases, greater, confidence)| {
+            Some((greater, confidence, offset + self.phrase(offset, phrases)?))
+        })
+    }
+
+    /// "more vowels than consonants" or "fewer consonants than vowels". Which letters are in
+    /// the majority is read from `vowel_ratio`; a string needs a consonant to have more
+    /// consonants, as one without letters has a ratio of 0.
+    fn vowel_balance(&mut self, negate: bool) -> bool {
+        let vocabulary = self.vocabulary;
+        // "no more vowels than consonants" is "not more vowels than consonants".
+        let no = self.is_word(0, &["no"]);
+        let negate = negate != no;
+        let offset = usize::from(no);
+        let (more, length) = match (
+            self.phrase(offset, &vocabulary.more),
+            self.phrase(offset, &vocabulary.fewer),
+        ) {
+            (Some(length), _) => (true, offset + length),
+            (None, Some(length)) => (false, offset + length),
+            (None, None) => return false,
+        };
+
+        let letters = |offset| match self.word(offset) {
+            Some("vowel" | "vowels") => Some(CharacterClass::Vowel),
+            Some("consonant" | "consonants") => Some(CharacterClass::Consonant),
+            _ => None,
+        };
+        let (Some(first), Some(second)) = (letters(length), letters(length + 2)) else {
+            return false;
+        };
+        if first == second || !self.is_word(length + 1, &["than"]) {
+            return false;
+        }
+
+        let balance = if more == (first == CharacterClass::Vowel) {
+            ratio("vowel_ratio", Comparison::Greater, 0.5)
+        } else {
+            and(
+                ratio("vowel_ratio", Comparison::Less, 0.5),
+                numeric("consonant_count", Comparison::GreaterOrEqual, 1),
+            )
+        };
+
+        self.clause()
+            .expressions
+            .push(if negate { not(balance) } else { balance });
+        self.consume(length + 3);
+
+        true
+    }
+
+    /// "tiny" or "lengthy", as configured in the vocabulary.
+    fn length_adjective(&mut self, negate: bool) -> bool {
+        let vocabulary = self.vocabulary;
+        let (short, length) = match (
+            self.phrase(0, &vocabulary.short.words),
+            self.phrase(0, &vocabulary.long.words),
+        ) {
+            (Some(length), _) => (true, length),
+            (None, Some(length)) => (false, length),
+            (None, None) => return false,
+        };
+
+        // "not tiny" keeps everything above the cap.
+        let (min, max) = match (short, negate) {
+            (true, false) => (None, Some(vocabulary.short.length)),
+            (true, true) => (Some(vocabulary.short.length + 1), None),
+            (false, false) => (Some(vocabulary.long.length), None),
+            (false, true) => (None, Some(vocabulary.long.length - 1)),
+        };
+
+        self.apply_bounds(Measure::Length(None), min, max);
+        self.consume(length);
+
+        true
+    }
+
+    /// "exactly 5 characters", or a number with a length unit: "with one hundred characters".
+    fn exact(&mut self, negate: bool) -> Result<bool, SyntaxError> {
+        let length = match self.phrase(0, &self.vocabulary.exactly) {
+            Some(length) => length,
+            None if !self.after_number()
+                && self.quantity(0)?.is_some_and(|(_, value_len)| {
+                    matches!(self.measure(value_len), (Measure::Length(_), 1..))
+                }) =>
+            {
+                0
+            }
+            None => return Ok(false),
+        };
+
+        let Some((value, value_len)) = self.quantity(length)? else {
+            return Err(self.error(
+                length,
+                format!("Expected a number after '{}'", self.quote(0..length)),
+            ));
+        };
+
+        let (measure, measure_len) = self.measure(length + value_len);
+        let measure = self.unstated_measure(measure, measure_len, 0.7);
+
+        if negate {
+            self.clause().expressions.push(not(numeric(
+                measure.column(),
+                Comparison::Equal,
+                value,
+            )));
+        } else if let Measure::Words = measure {
+            self.clause().filters.word_count = Some(value);
+        } else if let Measure::Class(class) = measure {
+            *self.clause().filters.class_bounds_mut(class).0 = Some(value);
+        } else {
+            self.apply_bounds(measure, Some(value), Some(value));
+        }
+        self.consume(length + value_len + measure_len);
+
+        Ok(true)
+    }
+
+    fn between(&mut self, negate: bool) -> Result<bool, SyntaxError> {
+        if !self.is_word(0, &["between"]) {
+            return Ok(false);
+        }
+
+        let Some((low, low_len)) = self.cardinal(1) else {
+            return Err(self.error(1, format!("Expected a number after '{}'", self.quote(0..1))));
+        };
+        let and_offset = 1 + low_len;
+        if !self.is_word(and_offset, &["and"]) {
+            return Err(self.error(
+                and_offset,
+                format!("Expected 'and' after '{}'", self.quote(0..and_offset)),
+            ));
+        }
+        let Some((high, high_len)) = self.cardinal(and_offset + 1) else {
+            return Err(self.error(
+                and_offset + 1,
+                format!(
+                    "Expected a number after '{}'",
+                    self.quote(0..and_offset + 1)
+                ),
+            ));
+        };
+
+        let (measure, measure_len) = self.measure(and_offset + 1 + high_len);
+        let measure = self.unstated_measure(measure, measure_len, 0.7);
+
+        self.apply_range(measure, low.min(high), low.max(high), negate);
+        self.consume(and_offset + 1 + high_len + measure_len);
+
+        Ok(true)
+    }
+
+    /// "about 10 characters": the number plus or minus `approximate_tolerance` percent,
+    /// and at least one.
+    fn approximate(&mut self, negate: bool) -> Result<bool, SyntaxError> {
+        let Some(length) = self.phrase(0, &self.vocabulary.approximately) else {
+            return Ok(false);
+        };
+
+        // Otherwise ordinary filler: "strings about cats".
+        let Some((value, value_len)) = self.quantity(length)? else {
+            return Ok(false);
+        };
+
+        let tolerance = tolerance_of(value, self.options.approximate_tolerance);
+        let (mut low, mut high) = (
+            (value - tolerance).max(0),
+            self.shift(value, tolerance, length..length + value_len)?,
+        );
+
+        if tolerance > 0 {
+            let reason = format!(
+                "'{}' was read as {} to {}; it could mean exactly {}",
+                self.quote(0..length + value_len),
+                low,
+                high,
+                value
+            );
+            if self.ambiguous(AmbiguityKind::Approximate, reason, 0.8) {
+                (low, high) = (value, value);
+            }
+        }
+
+        let (measure, measure_len) = self.measure(length + value_len);
+        let measure = self.unstated_measure(measure, measure_len, 0.7);
+
+        self.apply_range(measure, low, high, negate);
+        self.consume(length + value_len + measure_len);
+
+        Ok(true)
+    }
+
+    /// "added today", "from last week", "in the past 3 days", "before 2026-01-01" or
+    /// "since yesterday and before today"; every date is a UTC day.
+    fn created(&mut self, negate: bool) -> Result<bool, SyntaxError> {
+        let mut offset = usize::from(self.is_word(0, &["added", "created", "from", "during"]));
+        // "from the last two weeks"
+        if offset > 0 && self.is_word(offset, &["the"]) {
+            offset += 1;
+        }
+
+        let (after, before, length) = match self.word(offset) {
+            Some(keyword @ ("before" | "since" | "after")) => {
+                let Some((start, end, date_len)) = self.date(offset + 1) else {
+                    return Err(self.error(
+                        offset + 1,
+                        format!("Expected a date after '{}'", self.quote(offset..offset + 1)),
+                    ));
+                };
+                let length = offset + 1 + date_len;
+
+                if keyword == "before" {
+                    (None, Some(start), length)
+                } else {
+                    let after = if keyword == "since" { start } else { end };
+
+                    // "since X and before Y" is one range, so a negation covers both ends.
+                    let before_offset = length + usize::from(self.is_word(length, &["and"]));
+                    match self.date(before_offset + 1) {
+                        Some((before, _, before_len))
+                            if self.is_word(before_offset, &["before"]) =>
+                        {
+                            (Some(after), Some(before), before_offset + 1 + before_len)
+                        }
+                        _ => (Some(after), None, length),
+                    }
+                }
+            }
+            Some("on") => match self.date(offset + 1) {
+                Some((start, end, date_len)) => (Some(start), Some(end), offset + 1 + date_len),
+                None => return Ok(false),
+            },
+            Some("last" | "past") => match self.rolling(offset + 1) {
+                Some((start, rolling_len)) => (Some(start), None, offset + 1 + rolling_len),
+                None => match self.date(offset) {
+                    Some((start, end, date_len)) => (Some(start), Some(end), offset + date_len),
+                    None => return Ok(false),
+                },
+            },
+            _ => match self.date(offset) {
+                Some((start, end, date_len)) => (Some(start), Some(end), offset + date_len),
+                None => return Ok(false),
+            },
+        };
+
+        if negate {
+            let range = [
+                after.map(|after| created_at(Comparison::GreaterOrEqual, after)),
+                before.map(|before| created_at(Comparison::Less, before)),
+            ];
+            if let Some(range) = range.into_iter().flatten().reduce(and) {
+                self.clause().expressions.push(not(range));
+            }
+        } else {
+            let filters = &mut self.clause().filters;
+            filters.created_after = after.or(filters.created_after);
+            filters.created_before = before.or(filters.created_before);
+        }
+        self.consume(length);
+
+        Ok(true)
+    }
+
+    /// A day or calendar period at `offset` ("today", "2026-01-01", "last month"), as its
+    /// start, its exclusive end and the tokens it took. A full timestamp is a period of one
+    /// millisecond, the precision `created_at` is stored with.
+    fn date(&self, offset: usize) -> Option<(Timestamp, Timestamp, usize)> {
+        let today = self.now.date_naive();
+
+        let (period, back, length) = match (self.word(offset)?, self.word(offset + 1)) {
+            ("today", _) => (Period::Day, 0, 1),
+            ("yesterday", _) => (Period::Day, 1, 1),
+            ("this", Some(unit)) => (Period::parse(unit)?, 0, 2),
+            ("last", Some(unit)) => (Period::parse(unit)?, 1, 2),
+            (word, _) => {
+                let timestamp = Timestamp::parse(word).ok()?;
+                let end = match timestamp.date() {
+                    Some(date) => Timestamp::midnight(date.succ_opt()?),
+                    None => Timestamp(timestamp.0 + Duration::milliseconds(1)),
+                };
+                return Some((timestamp, end, 1));
+            }
+        };
+
+        let (start, end) = period.calendar(today, back)?;
+
+        Some((start, end, length))
+    }
+
+    /// "3 days", "two weeks" or "week" (one) after "last"/"past", as the instant that long
+    /// before now.
+    fn rolling(&self, offset: usize) -> Option<(Timestamp, usize)> {
+        let (count, count_len) = self.cardinal(offset).unwrap_or((1, 0));
+        let period = Period::parse(self.word(offset + count_len)?)?;
+        // "last week" without a number is the calendar week; hours have no calendar.
+        if count_len == 0 && self.is_word(offset - 1, &["last"]) && !matches!(period, Period::Hour)
+        {
+            return None;
+        }
+
+        let start = period.before(self.now, u32::try_from(count).ok()?)?;
+
+        Some((start, count_len + 1))
+    }
+
+    /// Bounds `measure` to `low..=high`, or excludes that range when negated.
+    fn apply_range(&mut self, measure: Measure, low: i32, high: i32, negate: bool) {
+        if negate {
+            let column = measure.column();
+            self.clause().expressions.push(not(and(
+                numeric(column, Comparison::GreaterOrEqual, low),
+                numeric(column, Comparison::LessOrEqual, high),
+            )));
+        } else {
+            self.apply_bounds(measure, Some(low), Some(high));
+        }
+    }
+
+    /// Reads an optional unit after a number, returning what it measures and its length.
+    fn measure(&self, offset: usize) -> (Measure, usize) {
+        if let Some((class, length)) = self.class_noun(offset) {
+            return (Measure::Class(class), length);
+        }
+
+        match (self.word(offset), self.word(offset + 1)) {
+            (Some("byte" | "bytes"), _) => (Measure::Length(Some(LengthUnit::Bytes)), 1),
+            (Some("codepoint" | "codepoints"), _) => {
+                (Measure::Length(Some(LengthUnit::Codepoints)), 1)
+            }
+            (Some("code"), Some("point" | "points")) => {
+                (Measure::Length(Some(LengthUnit::Codepoints)), 2)
+            }
+            (Some("grapheme" | "graphemes"), _) => {
+                (Measure::Length(Some(LengthUnit::Graphemes)), 1)
+            }
+            (Some("character" | "characters" | "char" | "chars" | "letter" | "letters"), _) => {
+                (Measure::Length(None), 1)
+            }
+            (Some("word" | "words"), _) => (Measure::Words, 1),
+            _ => (Measure::Length(None), 0),
+        }
+    }
+
+    fn apply_bounds(&mut self, measure: Measure, min: Option<i32>, max: Option<i32>) {
+        let filters = &mut self.clause().filters;
+
+        match measure {
+            Measure::Length(unit) => {
+                filters.length_unit = unit.or(filters.length_unit);
+                filters.min_length = min.or(filters.min_length);
+                filters.max_length = max.or(filters.max_length);
+            }
+            Measure::Words => {
+                filters.min_word_count = min.or(filters.min_word_count);
+                filters.max_word_count = max.or(filters.max_word_count);
+            }
+            Measure::Class(class) => {
+                let (_, class_min, class_max) = filters.class_bounds_mut(class);
+                *class_min = min.or(*class_min);
+                *class_max = max.or(*class_max);
+            }
+        }
+    }
+
+    /// Requires every one of `characters` along with those the clause already requires:
+    /// "containing a containing b" needs both. A single character stays in
+    /// `contains_character`.
+    fn require(&mut self, characters: &str) {
+        let filters = &mut self.clause().filters;
+
+        let previous = [
+            filters.contains_character.take(),
+            filters.contains_all.take(),
+        ];
+        let mut required = String::new();
+        for character in previous
+            .into_iter()
+            .flatten()
+            .collect::<String>()
+            .chars()
+            .chain(characters.chars())
+        {
+            if !required.contains(character) {
+                required.push(character);
+            }
+        }
+
+        if required.chars().count() == 1 {
+            filters.contains_character = Some(required);
+        } else {
+            filters.contains_all = Some(required);
+        }
+    }
+
+    /// Requires one of `characters`. `contains_any` holds a single choice, so a second one
+    /// ("containing a or b containing c or d") becomes an expression ORing the characters.
+    fn require_any(&mut self, characters: String) {
+        let clause = self.clause();
+        if clause.filters.contains_any.is_none() {
+            clause.filters.contains_any = Some(characters);
+            return;
+        }
+
+        let any = characters
+            .chars()
+            .map(|character| {
+                FilterExpression::Condition(Condition::Contains(vec![character.to_string()]))
+            })
+            .reduce(|left, right| FilterExpression::Or(Box::new(left), Box::new(right)));
+        clause.expressions.extend(any);
+    }
+
+    /// Reads a character class ("digits", "uppercase letters", "punctuation marks"),
+    /// returning it and how many tokens it took.
+    fn class_noun(&self, offset: usize) -> Option<(CharacterClass, usize)> {
+        let (class, takes_noun) = match self.word(offset)? {
+            "digit" | "digits" => (CharacterClass::Digit, false),
+            "space" | "spaces" => (CharacterClass::Whitespace, false),
+            "symbol" | "symbols" => (CharacterClass::Symbol, false),
+            "emoji" | "emojis" => (CharacterClass::Emoji, false),
+            "vowel" | "vowels" => (CharacterClass::Vowel, false),
+            "consonant" | "consonants" => (CharacterClass::Consonant, false),
+            "uppercase" | "upper-case" | "capital" | "capitals" => {
+                (CharacterClass::Uppercase, true)
+            }
+            "lowercase" | "lower-case" => (CharacterClass::Lowercase, true),
+            "punctuation" => (CharacterClass::Punctuation, true),
+            "whitespace" => (CharacterClass::Whitespace, true),
+            _ => return None,
+        };
+
+        let noun = takes_noun
+            && self.is_word(
+                offset + 1,
+                &[
+                    "letter",
+                    "letters",
+                    "character",
+                    "characters",
+                    "mark",
+                    "marks",
+                ],
+            );
+
+        Some((class, 1 + usize::from(noun)))
+    }
+
+    fn word_count(&mut self, negate: bool) -> bool {
+        let words = ["word", "words"];
+
+        let (count, length) = if let Some((count, count_len)) = self.cardinal(0)
+            && self.is_word(count_len, &words)
+        {
+            (count, count_len + 1)
+        } else if let Some(count) = self.word(0).and_then(|word| {
+            let prefix = word
+                .strip_suffix("-word")
+                .or_else(|| word.strip_suffix("-words"))?;
+            match prefix {
+                "single" => Some(1),
+                prefix => cardinal_word(prefix),
+            }
+        }) {
+            (count, 1)
+        } else if self.is_word(0, &["single"]) {
+            (1, if self.is_word(1, &words) { 2 } else { 1 })
+        } else {
+            return false;
+        };
+
+        if negate {
+            self.clause()
+                .expressions
+                .push(not(numeric("word_count", Comparison::Equal, count)));
+        } else {
+            self.clause().filters.word_count = Some(count);
+        }
+        self.consume(length);
+
+        true
+    }
+
+    /// "containing digits", "with no emoji", "without punctuation", "with 2 uppercase
+    /// letters" or "3 digits". A class with no lead or number is left to `letter_case`.
+    fn character_class(&mut self, negate: bool) -> bool {
+        let (mut excluded, mut offset) = match (
+            self.phrase(0, &self.vocabulary.contains),
+            self.phrase(0, &self.vocabulary.excludes),
+        ) {
+            (Some(length), _) => (false, length),
+            (None, Some(length)) => (true, length),
+            (None, None) => (false, 0),
+        };
+
+        if offset > 0 && self.is_word(offset, &["no"]) {
+            excluded = true;
+            offset += 1;
+        } else if offset > 0
+            && self.is_word(offset, &["a", "an", "any", "some"])
+            && self.class_noun(offset + 1).is_some()
+        {
+            offset += 1;
+        }
+
+        let (count, count_len) = match self.cardinal(offset) {
+            Some((count, count_len)) if !excluded => (Some(count), count_len),
+            _ => (None, 0),
+        };
+        let Some((class, class_len)) = self.class_noun(offset + count_len) else {
+            return false;
+        };
+        if offset == 0 && count.is_none() {
+            return false;
+        }
+
+        let mut length = offset + count_len + class_len;
+
+        let Some(count) = count else {
+            // "not containing digits" is "without digits".
+            let absent = excluded != negate;
+
+            // "with digits and symbols"; "without digits or symbols" has neither.
+            let mut classes = vec![class];
+            loop {
+                let comma = self
+                    .token(length)
+                    .is_some_and(|token| token.kind == TokenKind::Comma);
+                let separator_len = match self.word(length + usize::from(comma)) {
+                    Some("and") => usize::from(comma) + 1,
+                    Some("or") if absent => usize::from(comma) + 1,
+                    _ if comma => 1,
+                    _ => break,
+                };
+                let Some((class, class_len)) = self.class_noun(length + separator_len) else {
+                    break;
+                };
+                classes.push(class);
+                length += separator_len + class_len;
+            }
+
+            let (min, max) = if absent {
+                (None, Some(0))
+            } else {
+                (Some(1), None)
+            };
+            for class in classes {
+                self.apply_bounds(Measure::Class(class), min, max);
+            }
+            self.consume(length);
+
+            return true;
+        };
+
+        if negate {
+            self.clause()
+                .expressions
+                .push(not(numeric(class.column(), Comparison::Equal, count)));
+        } else {
+            *self.clause().filters.class_bounds_mut(class).0 = Some(count);
+        }
+        self.consume(length);
+
+        true
+    }
+
+    /// "all-caps" or "lowercase": at least one letter, and none of the other case.
+    fn letter_case(&mut self, negate: bool) -> bool {
+        let letters = ["letter", "letters", "character", "characters"];
+        let (upper, length) = match (self.word(0), self.word(1)) {
+            (Some("all"), Some("caps")) => (true, 2),
+            (Some("all-caps" | "allcaps" | "all-uppercase"), _) => (true, 1),
+            (Some("all-lowercase"), _) => (false, 1),
+            (Some("uppercase" | "upper-case"), _) if !self.is_word(1, &letters) => (true, 1),
+            (Some("lowercase" | "lower-case"), _) if !self.is_word(1, &letters) => (false, 1),
+            _ => return false,
+        };
+
+        let (cased, other) = if upper {
+            (CharacterClass::Uppercase, CharacterClass::Lowercase)
+        } else {
+            (CharacterClass::Lowercase, CharacterClass::Uppercase)
+        };
+
+        if negate {
+            self.clause().expressions.push(not(and(
+                numeric(cased.column(), Comparison::GreaterOrEqual, 1),
+                numeric(other.column(), Comparison::LessOrEqual, 0),
+            )));
+        } else {
+            self.apply_bounds(Measure::Class(cased), Some(1), None);
+            self.apply_bounds(Measure::Class(other), None, Some(0));
+        }
+        self.consume(length);
+
+        true
+    }
+
+    fn contains(&mut self, negate: bool) -> Result<bool, SyntaxError> {
+        let (mut excluded, mut offset) = match (
+            self.phrase(0, &self.vocabulary.contains),
+            self.phrase(0, &self.vocabulary.excludes),
+        ) {
+            (Some(length), _) => (negate, length),
+            (None, Some(length)) => (true, length),
+            (None, None) => return Ok(false),
+        };
+        let lead = self.text(0..offset);
+        // "with" is also ordinary filler: "strings with 3 words".
+        let filler = self.vocabulary.filler.contains(&lead);
+
+        if self.is_word(offset, &["no"]) {
+            excluded = true;
+            offset += 1;
+        }
+
+        let Some((characters, any, length)) = self.characters(offset) else {
+            if filler {
+                return Ok(false);
+            }
+            return Err(self.error(
+                offset,
+                format!(
+                    "Expected a single character after '{}'",
+                    self.quote(0..offset)
+                ),
+            ));
+        };
+
+        // "strings with a" may have been cut off before the noun "a" introduces.
+        if filler
+            && offset == 1
+            && length == 1
+            && self.is_word(offset, &["a"])
+            && self.ambiguous(
+                AmbiguityKind::Article,
+                format!(
+                    "'{}' was read as containing the letter a; 'a' could be an article",
+                    self.quote(0..offset + 1)
+                ),
+                0.5,
+            )
+        {
+            return Ok(false);
+        }
+        offset += length;
+
+        let count = match characters.as_slice() {
+            [_] if !excluded => self.count(offset)?,
+            _ => None,
+        };
+
+        let multiple = characters.len() > 1;
+        let characters = characters.into_iter().collect::<String>();
+        let filters = &mut self.clause().filters;
+
+        if excluded {
+            filters.excludes = Some(filters.excludes.take().unwrap_or_default() + &characters);
+        } else if multiple && any {
+            self.require_any(characters);
+        } else if let Some((min, max, count_len)) = count {
+            // A repeated count keeps the tighter bound: "e at least 2 times" and then "e at
+            // least 3 times" need 3.
+            if let Some(min) = min {
+                let min_counts = filters.min_char_count.get_or_insert_default();
+                let current = min_counts.0.entry(characters.clone()).or_insert(min);
+                *current = (*current).max(min);
+            }
+            if let Some(max) = max {
+                let max_counts = filters.max_char_count.get_or_insert_default();
+                let current = max_counts.0.entry(characters).or_insert(max);
+                *current = (*current).min(max);
+            }
+            offset += count_len;
+        } else {
+            self.require(&characters);
+        }
+        self.consume(offset);
+
+        Ok(true)
+    }
+
+    /// Reads a list of characters such as "x, y and z" or "x or y", returning the
+    /// characters, whether they were joined with "or", and how many tokens were read.
+    fn characters(&self, offset: usize) -> Option<(Vec<char>, bool, usize)> {
+        let (first, first_len) = self.character(offset)?;
+        let mut characters = vec![first];
+        let mut any = false;
+        let mut position = offset + first_len;
+
+        loop {
+            let comma = self
+                .token(position)
+                .is_some_and(|token| token.kind == TokenKind::Comma);
+
+            let (separator_len, or) = match (comma, self.word(position), self.word(position + 1)) {
+                (true, _, Some("and")) => (2, false),
+                (true, _, Some("or")) => (2, true),
+                (true, _, _) => (1, false),
+                (false, Some("and"), _) => (1, false),
+                (false, Some("or"), _) => (1, true),
+                _ => break,
+            };
+
+            let Some((character, length)) = self.character(position + separator_len) else {
+                break;
+            };
+
+            characters.push(character);
+            any |= or;
+            position += separator_len + length;
+        }
+
+        Some((characters, any, position - offset))
+    }
+
+    fn character(&self, offset: usize) -> Option<(char, usize)> {
+        let letter_words = ["letter", "letters", "character", "characters"];
+        let article = self.is_word(offset, &["the", "a"])
+            && (self.is_word(offset + 1, &letter_words)
+                || self.ordinal_character(offset + 1).is_some());
+        let mut position = offset + usize::from(article);
+
+        if let Some((character, length)) = self.ordinal_character(position) {
+            return Some((character, position + length - offset));
+        }
+
+        if self.is_word(position, &letter_words) && self.single_character(position + 1).is_some() {
+            position += 1;
+        }
+
+        let character = self.single_character(position)?;
+
+        Some((character, position + 1 - offset))
+    }
+
+    fn single_character(&self, offset: usize) -> Option<char> {
+        let token = self.token(offset)?;
+
+        // A number followed by a unit is a count ("with 3 words", "with a hundred
+        // characters"), not a character.
+        if let Some((_, length)) = self.cardinal(offset)
+            && (self.measure(offset + length).1 > 0
+                || self.is_word(offset + length, &["time", "times"]))
+        {
+            return None;
+        }
+
+        let mut chars = token.text.chars();
+        match (token.kind != TokenKind::Comma, chars.next(), chars.next()) {
+            (true, Some(character), None) => Some(character),
+            _ => None,
+        }
+    }
+
+    /// "first vowel", "3rd consonant", "twenty first letter" at `offset`, and how many
+    /// tokens it took.
+    fn ordinal_character(&self, offset: usize) -> Option<(char, usize)> {
+        let (ordinal, length) = self.ordinal_at(offset)?;
+        let position = ordinal.checked_sub(1)?;
+
+        let language = self.options.language.unwrap_or_default();
+        let character = match self.word(offset + length)? {
+            "vowel" => language.vowels().get(position),
+            "consonant" => language.consonants().get(position),
+            "letter" | "alphabet" => language.alphabet().get(position),
+            _ => None,
+        }
+        .copied()?;
+
+        Some((character, length + 1))
+    }
+
+    /// Reads an ordinal ("tenth", "12th", "twenty-first", "twenty first"), returning its
+    /// value and how many tokens it took.
+    fn ordinal_at(&self, offset: usize) -> Option<(usize, usize)> {
+        if let Some(ordinal) = self.word(offset).and_then(ordinal_word) {
+            return Some((ordinal, 1));
+        }
+
+        // "twenty first": a cardinal word prefix followed by a smaller ordinal word.
+        self.word(offset)?;
+        let (prefix, length) = self.cardinal(offset)?;
+        let ordinal = ordinal_word(self.word(offset + length)?)?;
+
+        compound_ordinal(usize::try_from(prefix).ok()?, ordinal).map(|value| (value, length + 1))
+    }
+
+    fn ordinal(&mut self, negate: bool) -> Result<bool, SyntaxError> {
+        let Some((_, length)) = self.ordinal_at(0) else {
+            return Ok(false);
+        };
+        let Some("vowel" | "consonant" | "letter" | "alphabet") = self.word(length) else {
+            return Ok(false);
+        };
+
+        let Some((character, _)) = self.ordinal_character(0) else {
+            return Err(SyntaxError {
+                reason: format!("There is no {}", self.quote(0..length + 1)),
+                span: self.span(0..length + 1),
+            });
+        };
+
+        let filters = &mut self.clause().filters;
+        if negate {
+            filters.excludes =
+                Some(filters.excludes.take().unwrap_or_default() + &character.to_string());
+        } else {
+            self.require(&character.to_string());
+        }
+        self.consume(length + 1);
+
+        Ok(true)
+    }
+
+    /// Reads a frequency threshold ("at least 3 times", "at most twice", "exactly 2 times",
+    /// "more than 1 time", "no more than 3 times"), returning the min and max occurrences and how many tokens it took.
+    fn count(&self, offset: usize) -> Result<Option<Count>, SyntaxError> {
+        let (qualifier, qualifier_len) = match (
+            self.word(offset),
+            self.word(offset + 1),
+            self.word(offset + 2),
+        ) {
+            (Some("at"), Some("least"), _) => ("min", 2),
+            (Some("at"), Some("most"), _) => ("max", 2),
+            (Some("more"), Some("than"), _) => ("above", 2),
+            (Some("fewer" | "less"), Some("than"), _) => ("below", 2),
+            (Some("no"), Some("more"), Some("than")) => ("max", 3),
+            (Some("no"), Some("fewer" | "less"), Some("than")) => ("min", 3),
+            (Some("exactly"), ..) => ("exact", 1),
+            _ => ("min", 0),
+        };
+
+        let position = offset + qualifier_len;
+        let (value, value_len, count_len) = match self.word(position) {
+            Some("once") => (1, 1, 1),
+            Some("twice") => (2, 1, 1),
+            Some("thrice") => (3, 1, 1),
+            _ => {
+                let Some((_, value_len)) = self.cardinal(position) else {
+                    return Ok(None);
+                };
+                if !self.is_word(position + value_len, &["time", "times"]) {
+                    return Ok(None);
+                }
+                let (value, _) = self.quantity(position)?.unwrap_or_default();
+                (value, value_len, value_len + 1)
+            }
+        };
+
+        let length = qualifier_len + count_len;
+        let number = position..position + value_len;
+
+        Ok(Some(match qualifier {
+            "min" => (Some(value), None, length),
+            "max" => (None, Some(value), length),
+            "above" => (Some(self.shift(value, 1, number)?), None, length),
+            // "fewer than 0 times" leaves no count to allow.
+            "below" if value == 0 => {
+                return Err(SyntaxError {
+                    reason: format!(
+                        "'{}' matches no string",
+                        self.quote(offset..offset + length)
+                    ),
+                    span: self.span(offset..offset + length),
+                });
+            }
+            "below" => (None, Some(value - 1), length),
+            _ => (Some(value), Some(value), length),
+        }))
+    }
+}
+
+/// The fewest and most occurrences a count allows, and how many tokens it took.
+type Count = (Option<i32>, Option<i32>, usize);
+
+/// How far "about `value`" may stray: `percent` of it, rounded, and at least one unless the
+/// tolerance is off.
+fn tolerance_of(value: i32, percent: u32) -> i32 {
+    match percent {
+        0 => 0,
+        percent => ((value as f64 * percent as f64 / 100.0).round() as i32).max(1),
+    }
+}
+
+/// A spelled-out number word, by the role it plays in the number.
+#[derive(Clone, Copy)]
+enum NumberWord {
+    /// "zero" to "nine".
+    Unit(i32),
+    /// "ten" to "nineteen".
+    Teen(i32),
+    /// "twenty" to "ninety".
+    Tens(i32),
+    Hundred,
+    /// "thousand" or "million".
+    Scale(i32),
+}
+
+fn number_word(word: &str) -> Option<NumberWord> {
+    const UNITS: [&str; 10] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    const TEENS: [&str; 10] = [
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 8] = [
+        "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+
+    let position = |words: &[&str]| words.iter().position(|w| *w == word).map(|i| i as i32);
+
+    if let Some(unit) = position(&UNITS) {
+        return Some(NumberWord::Unit(unit));
+    }
+    if let Some(teen) = position(&TEENS) {
+        return Some(NumberWord::Teen(10 + teen));
+    }
+    if let Some(tens) = position(&TENS) {
+        return Some(NumberWord::Tens(20 + tens * 10));
+    }
+
+    match word {
+        "hundred" => Some(NumberWord::Hundred),
+        "thousand" => Some(NumberWord::Scale(1_000)),
+        "million" => Some(NumberWord::Scale(1_000_000)),
+        _ => None,
+    }
+}
+
+/// Accumulates number words left to right, refusing orders no one would write, such as
+/// "five twenty", "hundred hundred" or "thousand million"; `Parser::read_cardinal` reports
+/// a refused word as an error rather than stopping before it.
+#[derive(Clone, Copy, Default)]
+struct Cardinal {
+    /// Sum of the groups already closed by a scale word.
+    total: i64,
+    /// The group below the last scale, e.g. 120 in "one thousand one hundred twenty".
+    current: i64,
+    last: Option<NumberWord>,
+    /// The last scale word, so scales only ever decrease.
+    scale: Option<i32>,
+    words: usize,
+}
+
+impl Cardinal {
+    fn push(&mut self, word: &str) -> bool {
+        let Some(number) = number_word(word) else {
+            return false;
+        };
+
+        let accepted = match (self.last, number) {
+            (Some(NumberWord::Unit(0)), _) => false,
+            (_, NumberWord::Unit(0)) => self.last.is_none(),
+            (None | Some(NumberWord::Hundred | NumberWord::Scale(_)), NumberWord::Unit(_))
+            | (Some(NumberWord::Tens(_)), NumberWord::Unit(_)) => true,
+            (
+                None | Some(NumberWord::Hundred | NumberWord::Scale(_)),
+                NumberWord::Teen(_) | NumberWord::Tens(_),
+            ) => true,
+            (Some(NumberWord::Unit(_) | NumberWord::Teen(_)), NumberWord::Hundred) => {
+                self.current < 100
+            }
+            (Some(NumberWord::Scale(_)), NumberWord::Scale(_)) => false,
+            (Some(_), NumberWord::Scale(scale)) => self.scale.is_none_or(|last| scale < last),
+            _ => false,
+        };
+        if !accepted {
+            return false;
+        }
+
+        match number {
+            NumberWord::Unit(value) | NumberWord::Teen(value) | NumberWord::Tens(value) => {
+                self.current += value as i64
+            }
+            NumberWord::Hundred => self.current *= 100,
+            NumberWord::Scale(scale) => {
+                self.total += self.current * scale as i64;
+                self.current = 0;
+                self.scale = Some(scale);
+            }
+        }
+        self.last = Some(number);
+        self.words += 1;
+
+        true
+    }
+
+    fn value(&self) -> Option<i32> {
+        i32::try_from(self.total + self.current).ok()
+    }
+}
+
+/// Whether every part of `word` is a number word: "million", "twenty-one".
+fn is_number_phrase_word(word: &str) -> bool {
+    word.split('-').all(|part| number_word(part).is_some())
+}
+
+/// Whether `word` is a number or ordinal on its own: "twelve", "twenty-one", "tenth".
+pub fn is_number_word(word: &str) -> bool {
+    cardinal_word(word).is_some() || ordinal_word(word).is_some()
+}
+
+/// A number written as a single word: "12", "five", "twenty-one".
+fn cardinal_word(word: &str) -> Option<i32> {
+    if let Ok(number) = word.parse::<i32>() {
+        return Some(number);
+    }
+
+    let mut cardinal = Cardinal::default();
+    if !word.split('-').all(|part| cardinal.push(part)) {
+        return None;
+    }
+
+    cardinal.value()
+}
+
+/// "first" to "nineteenth", "twentieth" to "ninetieth", "hundredth", a hyphenated
+/// compound such as "twenty-first", or a numeric ordinal such as "1st", "22nd" or "10th".
+fn ordinal_word(word: &str) -> Option<usize> {
+    const ORDINALS: [&str; 19] = [
+        "first",
+        "second",
+        "third",
+        "fourth",
+        "fifth",
+        "sixth",
+        "seventh",
+        "eighth",
+        "ninth",
+        "tenth",
+        "eleventh",
+        "twelfth",
+        "thirteenth",
+        "fourteenth",
+        "fifteenth",
+        "sixteenth",
+        "seventeenth",
+        "eighteenth",
+        "nineteenth",
+    ];
+    const TENS: [&str; 8] = [
+        "twentieth",
+        "thirtieth",
+        "fortieth",
+        "fiftieth",
+        "sixtieth",
+        "seventieth",
+        "eightieth",
+        "ninetieth",
+    ];
+
+    if let Some(position) = ORDINALS.iter().position(|ordinal| *ordinal == word) {
+        return Some(position + 1);
+    }
+    if let Some(position) = TENS.iter().position(|ordinal| *ordinal == word) {
+        return Some(20 + position * 10);
+    }
+    if word == "hundredth" {
+        return Some(100);
+    }
+
+    if let Some((prefix, last)) = word.rsplit_once('-') {
+        let prefix = usize::try_from(cardinal_word(prefix)?).ok()?;
+        return compound_ordinal(prefix, ordinal_word(last)?);
+    }
+
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))?;
+    digits.parse::<usize>().ok()
+}
+
+/// Adds a cardinal prefix to a smaller ordinal ("twenty" + "first"), as long as the
+/// ordinal fills a place the prefix leaves empty.
+fn compound_ordinal(prefix: usize, ordinal: usize) -> Option<usize> {
+    let valid = match ordinal {
+        1..=9 => prefix >= 20 && prefix.is_multiple_of(10),
+        10..=99 => prefix >= 100 && prefix.is_multiple_of(100),
+        _ => false,
+    };
+
+    valid.then_some(prefix + ordinal)
+}
+
+/// A span of time named in "last 3 days" or "this month".
+#[derive(Clone, Copy)]
+enum Period {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "hour" | "hours" => Some(Period::Hour),
+            "day" | "days" => Some(Period::Day),
+            "week" | "weeks" => Some(Period::Week),
+            "month" | "months" => Some(Period::Month),
+            "year" | "years" => Some(Period::Year),
+            _ => None,
+        }
+    }
+
+    /// Start and exclusive end of the calendar period `back` periods before the one holding
+    /// `today`. Weeks start on Monday.
+    fn calendar(&self, today: NaiveDate, back: u32) -> Option<(Timestamp, Timestamp)> {
+        let (start, end) = match self {
+            Period::Hour => return None,
+            Period::Day => {
+                let start = today - Duration::days(back.into());
+                (start, start.succ_opt()?)
+            }
+            Period::Week => {
+                let monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+                let start = monday - Duration::weeks(back.into());
+                (start, start + Duration::weeks(1))
+            }
+            Period::Month | Period::Year => {
+                let (first, months) = match self {
+                    Period::Month => (today.with_day(1)?, 1),
+                    _ => (today.with_ordinal(1)?, 12),
+                };
+                let start = first.checked_sub_months(Months::new(months * back))?;
+                (start, start.checked_add_months(Months::new(months))?)
+            }
+        };
+
+        Some((Timestamp::midnight(start), Timestamp::midnight(end)))
+    }
+
+    /// The instant `count` periods before `now`.
+    fn before(&self, now: DateTime<Utc>, count: u32) -> Option<Timestamp> {
+        let start = match self {
+            Period::Hour => now.checked_sub_signed(Duration::hours(count.into()))?,
+            Period::Day => now.checked_sub_signed(Duration::days(count.into()))?,
+            Period::Week => now.checked_sub_signed(Duration::weeks(count.into()))?,
+            Period::Month => now.checked_sub_months(Months::new(count))?,
+            Period::Year => now.checked_sub_months(Months::new(count.checked_mul(12)?))?,
+        };
+
+        Some(Timestamp(start))
+    }
+}
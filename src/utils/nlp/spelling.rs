@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    models::nlp::Correction,
+    utils::{
+        nlp::{
+            lexer::{Token, TokenKind},
+            parser::{KEYWORDS, is_number_word},
+            vocabulary::Vocabulary,
+        },
+        similarity::edit_distance,
+    },
+};
+
+/// Shorter words are left alone: "long" is two edits from "longer" and one from "song", so
+/// there is no telling what was meant.
+const MIN_CORRECTED_LENGTH: usize = 5;
+/// Words this long may be two edits away from their keyword ("palindrms").
+const LONG_WORD_LENGTH: usize = 8;
+
+/// Replaces each word the parser would not recognise with the keyword it is closest to,
+/// e.g. "shorer" with "shorter" or "palidromes" with "palindromes", and returns what was
+/// replaced. A word is only corrected when the closest keywords are within one edit (two
+/// for long words) and all mean the same thing.
+pub fn correct_spelling(tokens: &mut [Token], vocabulary: &Vocabulary) -> Vec<Correction> {
+    // Keyword to the group it belongs to; a grammar word is a group of its own.
+    let keywords = vocabulary
+        .keywords()
+        .map(|(group, word)| (word, group))
+        .chain(KEYWORDS.map(|word| (word, word)))
+        .collect::<BTreeMap<&str, &str>>();
+
+    tokens
+        .iter_mut()
+        .filter(|token| token.kind == TokenKind::Word)
+        .filter(|token| {
+            !keywords.contains_key(token.text.as_str())
+                && !vocabulary.filler.contains(&token.text)
+                && !is_number_word(&token.text)
+        })
+        .filter_map(|token| {
+            let corrected = closest_keyword(&token.text, &keywords)?.to_string();
+            let correction = Correction {
+                reason: format!("interpreted '{}' as '{}'", token.text, corrected),
+                text: std::mem::replace(&mut token.text, corrected.clone()),
+                corrected,
+                span: token.span,
+            };
+            Some(correction)
+        })
+        .collect()
+}
+
+fn closest_keyword<'a>(word: &str, keywords: &BTreeMap<&'a str, &str>) -> Option<&'a str> {
+    let length = word.chars().count();
+    if length < MIN_CORRECTED_LENGTH
+        || !word
+            .chars()
+            .all(|c| c.is_alphabetic() || matches!(c, '-' | '\''))
+    {
+        return None;
+    }
+
+    let allowed = if length >= LONG_WORD_LENGTH { 2 } else { 1 };
+    let mut closest = Vec::new();
+    let mut best = allowed + 1;
+
+    for (keyword, group) in keywords {
+        if keyword.chars().count().abs_diff(length) > allowed as usize {
+            continue;
+        }
+
+        let distance = edit_distance(word, keyword);
+        if distance < best {
+            best = distance;
+            closest.clear();
+        }
+        if distance == best {
+            closest.push((*keyword, *group));
+        }
+    }
+
+    // "containg" is as close to "contain" as to "contains", which mean the same; "fewes"
+    // could be "fewer" or "fewest" and is left unread.
+    let (keyword, group) = *closest.first()?;
+    closest
+        .iter()
+        .all(|(_, other)| *other == group)
+        .then_some(keyword)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::correct_spelling;
+    use crate::utils::nlp::{lexer::tokenise, vocabulary::Vocabulary};
+
+    /// Each corrected word of `query` and what it was read as.
+    fn corrections(query: &str) -> Vec<(String, String)> {
+        let mut tokens = tokenise(query);
+
+        correct_spelling(&mut tokens, &Vocabulary::default())
+            .into_iter()
+            .map(|correction| (correction.text, correction.corrected))
+            .collect()
+    }
+
+    #[test]
+    fn corrects_misspelt_keywords() {
+        for (query, text, corrected) in [
+            ("shorer than 10", "shorer", "shorter"),
+            ("palidromes", "palidromes", "palindromes"),
+            ("strings lnger than 5", "lnger", "longer"),
+            ("palindrms", "palindrms", "palindrome"),
+        ] {
+            assert_eq!(
+                corrections(query),
+                [(text.to_string(), corrected.to_string())],
+                "{}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn leaves_short_words_alone() {
+        // Close to "long", "most" and "than", but shorter than MIN_CORRECTED_LENGTH.
+        for query in ["lomg strings", "mots vowels", "shorter thn 5"] {
+            assert_eq!(corrections(query), [], "{}", query);
+        }
+    }
+
+    #[test]
+    fn leaves_words_with_digits_alone() {
+        for query in ["short3r than 5", "l0nger strings"] {
+            assert_eq!(corrections(query), [], "{}", query);
+        }
+    }
+
+    #[test]
+    fn keeps_known_words() {
+        for query in [
+            "strings that don't contain z",
+            "words that doesn't contain z",
+            "strings that dont contain z",
+            "strings that doesnt contain z",
+            "palindromes longer than twenty characters",
+            "the seventh letter is a vowel",
+            "non-palindromic strings",
+        ] {
+            assert_eq!(corrections(query), [], "{}", query);
+        }
+    }
+
+    #[test]
+    fn leaves_ambiguous_words_unread() {
+        // "fewer" and "fewest"; "longer" and "longest".
+        for query in ["fewes vowels", "longes strings"] {
+            assert_eq!(corrections(query), [], "{}", query);
+        }
+    }
+
+    #[test]
+    fn reads_equivalent_keywords_alike() {
+        // "contain" and "contains" mean the same, so the first of them is taken.
+        assert_eq!(
+            corrections("containg z"),
+            [("containg".to_string(), "contain".to_string())]
+        );
+    }
+}
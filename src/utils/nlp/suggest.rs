@@ -0,0 +1,405 @@
+use std::{cmp::Reverse, collections::HashSet};
+
+use chrono::Utc;
+
+use crate::{
+    models::nlp::Suggestion,
+    utils::nlp::{
+        Language, NlpOptions, describe_filters,
+        lexer::{Token, TokenKind, tokenise},
+        parse_natural_language,
+        parser::is_number_word,
+        vocabulary::Vocabulary,
+    },
+};
+
+/// Phrases the grammar reads, most useful first. `{group}` stands for each phrase of that
+/// vocabulary group; `N` and `M` for numbers, `X` for a character and `DATE` for a date.
+const TEMPLATES: [&str; 43] = [
+    "{palindrome}",
+    "{longer} than N characters",
+    "{shorter} than N characters",
+    "{contains} the letter X",
+    "N words",
+    "single word",
+    "{longest}",
+    "{shortest}",
+    "{newest}",
+    "{oldest}",
+    "{alphabetical}",
+    "{non_palindrome}",
+    "{negation} {palindrome}",
+    "{more} than N words",
+    "{fewer} than N words",
+    "{exactly} N characters",
+    "{exactly} N words",
+    "between N and M characters",
+    "between N and M words",
+    "{approximately} N characters",
+    "{excludes} the letter X",
+    "{contains} X at least N times",
+    "{contains} the first vowel",
+    "{contains} digits",
+    "{excludes} digits",
+    "all-caps",
+    "{more} vowels than consonants",
+    "{excludes} vowels",
+    "{short}",
+    "{long}",
+    "the N {longest}",
+    "top N",
+    "with the most words",
+    "with the fewest words",
+    "with the most unique characters",
+    "added today",
+    "added yesterday",
+    "added this week",
+    "added last month",
+    "in the last N days",
+    "added since DATE",
+    "added before DATE",
+    "strict {palindrome}",
+];
+
+/// A template with each group replaced by one of its phrases.
+struct Phrasing {
+    words: Vec<String>,
+    /// Sum of the phrases' positions in their groups; the first phrase of each is preferred.
+    rank: usize,
+}
+
+struct Candidate {
+    text: String,
+    example: String,
+    /// Typed tokens the template matched.
+    matched: usize,
+    rank: usize,
+    template: usize,
+}
+
+/// Completes a partially typed English query with the grammar phrases it could continue
+/// into: "strings longer th" gives "strings longer than N characters". The typed words
+/// matching the start of a phrase are completed; after a space any phrase may follow.
+/// Only completions that parse with every word used are returned, those matching most of
+/// the typed words first.
+pub fn suggest_completions(
+    prefix: &str,
+    options: &NlpOptions,
+    vocabulary: &Vocabulary,
+    limit: usize,
+) -> Vec<Suggestion> {
+    let options = NlpOptions {
+        language: Some(Language::English),
+        ..*options
+    };
+    let tokens = tokenise(prefix);
+    // The last word is unfinished unless a space follows it.
+    let partial = !prefix.ends_with(char::is_whitespace)
+        && tokens
+            .last()
+            .is_some_and(|token| token.kind != TokenKind::Comma);
+    let date = Utc::now().format("%Y-%m-%d").to_string();
+
+    let mut candidates = TEMPLATES
+        .iter()
+        .enumerate()
+        .flat_map(|(template, text)| {
+            phrasings(text, vocabulary)
+                .into_iter()
+                .map(move |phrasing| (template, phrasing))
+        })
+        .flat_map(|(template, phrasing)| {
+            (0..=tokens.len())
+                .filter_map(|start| {
+                    complete(prefix, &tokens[start..], partial, &phrasing.words, &date)
+                })
+                .map(|(text, example, matched)| Candidate {
+                    text,
+                    example,
+                    matched,
+                    rank: phrasing.rank,
+                    template,
+                })
+                .collect::<Vec<Candidate>>()
+        })
+        .collect::<Vec<Candidate>>();
+
+    candidates.sort_by_key(|candidate| {
+        (
+            Reverse(candidate.matched),
+            candidate.rank,
+            candidate.template,
+        )
+    });
+
+    // A phrase added after a finished query must change what it filters for.
+    let typed = parse_natural_language(prefix, &options, vocabulary)
+        .ok()
+        .map(|parsed| parsed.filters.applied());
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|candidate| seen.insert(candidate.text.clone()))
+        .filter_map(|candidate| {
+            // "strings longer the N longest" parses, but leaves "longer" unread.
+            let parsed = parse_natural_language(&candidate.example, &options, vocabulary)
+                .ok()
+                .filter(|parsed| parsed.unused_tokens.is_empty())
+                .filter(|parsed| {
+                    candidate.matched > 0 || typed.as_ref() != Some(&parsed.filters.applied())
+                })?;
+            Some(Suggestion {
+                text: candidate.text,
+                example: candidate.example,
+                description: describe_filters(&parsed.filters),
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Every way of filling a template's groups from the vocabulary; none when a group is
+/// empty, as the adjectives are by default.
+fn phrasings(template: &str, vocabulary: &Vocabulary) -> Vec<Phrasing> {
+    let mut phrasings = vec![Phrasing {
+        words: Vec::new(),
+        rank: 0,
+    }];
+
+    for part in template.split(' ') {
+        let choices = match part
+            .strip_prefix('{')
+            .and_then(|part| part.strip_suffix('}'))
+        {
+            Some(group) => vocabulary
+                .group(group)
+                .unwrap_or_default()
+                .iter()
+                .map(String::as_str)
+                .collect(),
+            None => vec![part],
+        };
+
+        phrasings = phrasings
+            .iter()
+            .flat_map(|phrasing| {
+                choices
+                    .iter()
+                    .enumerate()
+                    .map(|(position, choice)| Phrasing {
+                        words: phrasing
+                            .words
+                            .iter()
+                            .cloned()
+                            .chain(choice.split(' ').map(str::to_string))
+                            .collect(),
+                        rank: phrasing.rank + position,
+                    })
+            })
+            .collect();
+    }
+
+    phrasings
+}
+
+/// Completes `prefix` with `words` when its last tokens, `tail`, match their start; the
+/// completion, its example and how many tokens matched. A phrase may also start after a
+/// space with nothing matched.
+fn complete(
+    prefix: &str,
+    tail: &[Token],
+    partial: bool,
+    words: &[String],
+    date: &str,
+) -> Option<(String, String, usize)> {
+    let (typed, last) = match partial {
+        true => {
+            let (last, typed) = tail.split_last()?;
+            (typed, Some(last))
+        }
+        false => (tail, None),
+    };
+
+    if typed.len() >= words.len()
+        || !typed
+            .iter()
+            .zip(words)
+            .all(|(token, word)| matches(token, word, false))
+    {
+        return None;
+    }
+
+    let rest = &words[typed.len()..];
+    let (mut text, mut example, rest) = match last {
+        Some(last) => {
+            if !matches(last, &rest[0], true) {
+                return None;
+            }
+
+            // Keep what was typed before the unfinished word, and the word itself when it
+            // fills a placeholder.
+            let before = prefix.chars().take(last.span.start).collect::<String>();
+            let word = match sample(&rest[0], date) {
+                Some(_) => prefix
+                    .chars()
+                    .skip(last.span.start)
+                    .take(last.span.end - last.span.start)
+                    .collect::<String>(),
+                None => rest[0].clone(),
+            };
+            (before.clone() + &word, before + &word, &rest[1..])
+        }
+        None => {
+            let before = prefix.trim_end();
+            (before.to_string(), before.to_string(), rest)
+        }
+    };
+
+    for word in rest {
+        if !text.is_empty() {
+            text.push(' ');
+            example.push(' ');
+        }
+        text.push_str(word);
+        example.push_str(sample(word, date).unwrap_or(word));
+    }
+
+    (text.trim_end() != prefix.trim_end()).then_some((text, example, tail.len()))
+}
+
+/// Whether a typed token fits a template word; an unfinished one only needs to start it.
+fn matches(token: &Token, word: &str, partial: bool) -> bool {
+    match word {
+        "N" | "M" => matches!(token.kind, TokenKind::Number(_)) || is_number_word(&token.text),
+        "X" => token.kind == TokenKind::Quoted || token.text.chars().count() == 1,
+        "DATE" => token.text.chars().all(|c| c.is_ascii_digit() || c == '-'),
+        word if partial => token.kind == TokenKind::Word && word.starts_with(&token.text),
+        word => token.text == word,
+    }
+}
+
+/// The value a placeholder is given in the example.
+fn sample<'a>(word: &str, date: &'a str) -> Option<&'a str> {
+    match word {
+        "N" => Some("5"),
+        "M" => Some("10"),
+        "X" => Some("a"),
+        "DATE" => Some(date),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::suggest_completions;
+    use crate::{
+        models::nlp::Suggestion,
+        utils::nlp::{NlpOptions, Vocabulary, parse_natural_language},
+    };
+
+    fn suggestions(prefix: &str, limit: usize) -> Vec<Suggestion> {
+        suggest_completions(
+            prefix,
+            &NlpOptions::default(),
+            &Vocabulary::default(),
+            limit,
+        )
+    }
+
+    fn texts(prefix: &str) -> Vec<String> {
+        suggestions(prefix, 10)
+            .into_iter()
+            .map(|suggestion| suggestion.text)
+            .collect()
+    }
+
+    #[test]
+    fn completes_unfinished_words() {
+        let suggestions = suggestions("strings longer th", 10);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].text, "strings longer than N characters");
+        assert_eq!(suggestions[0].example, "strings longer than 5 characters");
+        assert_eq!(
+            suggestions[0].description,
+            "strings longer than 5 characters"
+        );
+
+        for (prefix, expected) in [
+            ("palin", &["palindrome", "palindromes", "palindromic"][..]),
+            ("sho", &["shorter than N characters", "shortest"]),
+            ("the 3 long", &["the 3 longest"]),
+            ("contains the let", &["contains the letter X"]),
+            ("added ye", &["added yesterday"]),
+            (
+                "not pal",
+                &["not palindrome", "not palindromes", "not palindromic"],
+            ),
+            // The number is kept as typed rather than replaced by N.
+            (
+                "strings longer than 5",
+                &[
+                    "strings longer than 5 characters",
+                    "strings longer than 5 words",
+                ],
+            ),
+        ] {
+            assert_eq!(texts(prefix), expected, "{}", prefix);
+        }
+    }
+
+    #[test]
+    fn continues_after_a_space() {
+        assert_eq!(
+            texts("between 3 and ")[..2],
+            ["between 3 and M characters", "between 3 and M words"]
+        );
+        assert_eq!(
+            texts("strings longer than 5 ")[0],
+            "strings longer than 5 characters"
+        );
+
+        // Each phrase added to a finished query changes what it filters for.
+        let typed = |query: &str| {
+            parse_natural_language(query, &NlpOptions::default(), &Vocabulary::default())
+                .unwrap()
+                .filters
+                .applied()
+        };
+        let prefix = "strings longer than 5 characters ";
+        let suggestions = suggestions(prefix, 10);
+        assert!(!suggestions.is_empty());
+        for suggestion in suggestions {
+            assert_ne!(
+                typed(&suggestion.example),
+                typed(prefix),
+                "{}",
+                suggestion.text
+            );
+        }
+    }
+
+    #[test]
+    fn suggests_nothing_that_does_not_parse() {
+        assert_eq!(texts("xyzzy"), Vec::<String>::new());
+        assert_eq!(
+            texts("strings longer than 5 characters"),
+            Vec::<String>::new()
+        );
+
+        for suggestion in suggestions("", 50) {
+            let parsed = parse_natural_language(
+                &suggestion.example,
+                &NlpOptions::default(),
+                &Vocabulary::default(),
+            )
+            .unwrap_or_else(|e| panic!("'{}' does not parse: {}", suggestion.example, e.reason));
+            assert!(parsed.unused_tokens.is_empty(), "{}", suggestion.example);
+        }
+    }
+
+    #[test]
+    fn limits_suggestions() {
+        assert_eq!(suggestions("", 5).len(), 5);
+        assert_eq!(suggestions("palin", 2).len(), 2);
+    }
+}
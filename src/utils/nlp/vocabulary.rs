@@ -0,0 +1,298 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Words and phrases the natural language parser recognises, grouped by the filter they
+/// stand for. A phrase may span several words ("mirror words"). Measure words, palindrome
+/// modes, dates and the grammar's own words ("than", "between", "or") are fixed.
+///
+/// Loaded from `NLP_VOCABULARY_PATH` (`.toml` or `.json`); a group left out of the file
+/// keeps its default words, a group given replaces them.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Vocabulary {
+    /// Negates the filter after it: "not".
+    pub negation: Vec<String>,
+    pub palindrome: Vec<String>,
+    pub non_palindrome: Vec<String>,
+    /// "longer than N"; a number without a unit is read as characters.
+    pub longer: Vec<String>,
+    /// "more than N"; just as likely to count words, so read with less confidence.
+    pub more: Vec<String>,
+    pub shorter: Vec<String>,
+    pub fewer: Vec<String>,
+    pub exactly: Vec<String>,
+    /// "about N", a range around N.
+    pub approximately: Vec<String>,
+    pub contains: Vec<String>,
+    pub excludes: Vec<String>,
+    pub longest: Vec<String>,
+    pub shortest: Vec<String>,
+    pub newest: Vec<String>,
+    pub oldest: Vec<String>,
+    pub alphabetical: Vec<String>,
+    /// Adjectives such as "tiny" that cap the length.
+    pub short: LengthAdjective,
+    /// Adjectives such as "lengthy" that set a minimum length.
+    pub long: LengthAdjective,
+    /// Single words skipped without lowering the confidence: "strings", "the", "with".
+    pub filler: Vec<String>,
+}
+
+/// Length adjectives and the bound they set, in characters.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LengthAdjective {
+    pub words: Vec<String>,
+    /// `max_length` for short words, `min_length` for long ones.
+    pub length: i32,
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        let words = |words: &[&str]| words.iter().map(|word| word.to_string()).collect();
+
+        Self {
+            negation: words(&[
+                "not", "non", "isn't", "aren't", "don't", "doesn't", "isnt", "arent", "dont",
+                "doesnt",
+            ]),
+            palindrome: words(&["palindrome", "palindromes", "palindromic"]),
+            non_palindrome: words(&[
+                "non-palindrome",
+                "non-palindromes",
+                "non-palindromic",
+                "nonpalindromic",
+            ]),
+            longer: words(&["longer", "bigger", "larger"]),
+            more: words(&["more", "greater"]),
+            shorter: words(&["shorter", "smaller"]),
+            fewer: words(&["less", "fewer"]),
+            exactly: words(&["exactly", "equals"]),
+            approximately: words(&[
+                "about",
+                "around",
+                "approximately",
+                "approx",
+                "roughly",
+                "circa",
+            ]),
+            contains: words(&["contain", "contains", "containing", "with"]),
+            excludes: words(&["without", "exclude", "excludes", "excluding"]),
+            longest: words(&["longest"]),
+            shortest: words(&["shortest"]),
+            newest: words(&["newest", "latest", "recent"]),
+            oldest: words(&["oldest", "earliest"]),
+            alphabetical: words(&["alphabetical", "alphabetically"]),
+            short: LengthAdjective {
+                words: Vec::new(),
+                length: 10,
+            },
+            long: LengthAdjective {
+                words: Vec::new(),
+                length: 30,
+            },
+            filler: words(&[
+                ",", "a", "all", "an", "and", "any", "are", "be", "entries", "entry", "find",
+                "get", "give", "has", "have", "having", "in", "is", "list", "me", "of", "only",
+                "show", "string", "strings", "text", "texts", "that", "the", "those", "value",
+                "values", "which", "whose", "with",
+            ]),
+        }
+    }
+}
+
+impl Vocabulary {
+    /// Reads a vocabulary file, choosing the format by its extension.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str::<Vocabulary>(&contents).map_err(|e| e.to_string()),
+            Some("json") => {
+                serde_json::from_str::<Vocabulary>(&contents).map_err(|e| e.to_string())
+            }
+            _ => Err("expected a .toml or .json file".to_string()),
+        }
+        .and_then(Vocabulary::normalised)
+        .map_err(|e| format!("Invalid vocabulary '{}': {}", path.display(), e))
+    }
+
+    /// Lowercases every phrase and collapses its whitespace, rejecting empty phrases,
+    /// multi-word filler and phrases given to two groups.
+    fn normalised(mut self) -> Result<Self, String> {
+        let mut owners = HashMap::new();
+
+        for (group, phrases) in self.groups_mut() {
+            for phrase in phrases.iter_mut() {
+                *phrase = phrase
+                    .split_whitespace()
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+                    .to_lowercase();
+
+                if phrase.is_empty() {
+                    return Err(format!("'{}' contains an empty phrase", group));
+                }
+
+                if let Some(owner) = owners.insert(phrase.clone(), group)
+                    && owner != group
+                {
+                    return Err(format!(
+                        "'{}' is in both '{}' and '{}'",
+                        phrase, owner, group
+                    ));
+                }
+            }
+        }
+
+        for word in &mut self.filler {
+            *word = word.trim().to_lowercase();
+            if word.is_empty() || word.contains(char::is_whitespace) {
+                return Err(format!("filler '{}' must be a single word", word));
+            }
+        }
+
+        if self.short.length < 0 || self.long.length < 0 {
+            return Err("Length adjectives need a length of at least 0".to_string());
+        }
+
+        Ok(self)
+    }
+
+    /// Each word of every phrase outside `filler` with its group, e.g. "mirror" and
+    /// "words" for "mirror words".
+    pub fn keywords(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.groups().into_iter().flat_map(|(group, phrases)| {
+            phrases
+                .iter()
+                .flat_map(|phrase| phrase.split(' '))
+                .map(move |word| (group, word))
+        })
+    }
+
+    /// The phrases of a group, e.g. `"longer"`; `None` for an unknown group or `filler`.
+    pub fn group(&self, name: &str) -> Option<&[String]> {
+        self.groups()
+            .into_iter()
+            .find(|(group, _)| *group == name)
+            .map(|(_, phrases)| phrases.as_slice())
+    }
+
+    /// Every group but `filler`, which may overlap with the others ("with").
+    fn groups(&self) -> [(&'static str, &Vec<String>); 18] {
+        [
+            ("negation", &self.negation),
+            ("palindrome", &self.palindrome),
+            ("non_palindrome", &self.non_palindrome),
+            ("longer", &self.longer),
+            ("more", &self.more),
+            ("shorter", &self.shorter),
+            ("fewer", &self.fewer),
+            ("exactly", &self.exactly),
+            ("approximately", &self.approximately),
+            ("contains", &self.contains),
+            ("excludes", &self.excludes),
+            ("longest", &self.longest),
+            ("shortest", &self.shortest),
+            ("newest", &self.newest),
+            ("oldest", &self.oldest),
+            ("alphabetical", &self.alphabetical),
+            ("short", &self.short.words),
+            ("long", &self.long.words),
+        ]
+    }
+
+    fn groups_mut(&mut self) -> [(&'static str, &mut Vec<String>); 18] {
+        [
+            ("negation", &mut self.negation),
+            ("palindrome", &mut self.palindrome),
+            ("non_palindrome", &mut self.non_palindrome),
+            ("longer", &mut self.longer),
+            ("more", &mut self.more),
+            ("shorter", &mut self.shorter),
+            ("fewer", &mut self.fewer),
+            ("exactly", &mut self.exactly),
+            ("approximately", &mut self.approximately),
+            ("contains", &mut self.contains),
+            ("excludes", &mut self.excludes),
+            ("longest", &mut self.longest),
+            ("shortest", &mut self.shortest),
+            ("newest", &mut self.newest),
+            ("oldest", &mut self.oldest),
+            ("alphabetical", &mut self.alphabetical),
+            ("short", &mut self.short.words),
+            ("long", &mut self.long.words),
+        ]
+    }
+}
+
+/// The vocabulary in use, swapped out whole when the file is reloaded so a query in flight
+/// keeps the vocabulary it started with.
+#[derive(Clone)]
+pub struct VocabularyStore {
+    /// `None` when the built-in defaults are used.
+    path: Option<PathBuf>,
+    active: Arc<RwLock<LoadedVocabulary>>,
+}
+
+#[derive(Clone)]
+pub struct LoadedVocabulary {
+    pub vocabulary: Arc<Vocabulary>,
+    pub loaded_at: DateTime<Utc>,
+}
+
+impl VocabularyStore {
+    pub fn load(path: Option<PathBuf>) -> Result<Self, String> {
+        let vocabulary = match &path {
+            Some(path) => Vocabulary::from_file(path)?,
+            None => Vocabulary::default(),
+        };
+
+        Ok(Self {
+            path,
+            active: Arc::new(RwLock::new(LoadedVocabulary {
+                vocabulary: Arc::new(vocabulary),
+                loaded_at: Utc::now(),
+            })),
+        })
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn current(&self) -> LoadedVocabulary {
+        self.active
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Re-reads the file. An invalid file leaves the current vocabulary in place.
+    pub fn reload(&self) -> Result<LoadedVocabulary, String> {
+        let vocabulary = match &self.path {
+            Some(path) => Vocabulary::from_file(path)?,
+            None => Vocabulary::default(),
+        };
+
+        let loaded = LoadedVocabulary {
+            vocabulary: Arc::new(vocabulary),
+            loaded_at: Utc::now(),
+        };
+        *self
+            .active
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = loaded.clone();
+
+        Ok(loaded)
+    }
+}
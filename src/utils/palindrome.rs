@@ -0,0 +1,95 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::models::filters::PalindromeMode;
+
+pub fn is_palindrome(input: &str, mode: PalindromeMode) -> bool {
+    match mode {
+        PalindromeMode::Strict => is_strict_palindrome(input),
+        PalindromeMode::Loose => is_loose_palindrome(input),
+        PalindromeMode::Normalised => is_normalised_palindrome(input),
+    }
+}
+
+/// Case-insensitive, character-by-character comparison. Whitespace and punctuation count.
+fn is_strict_palindrome(input: &str) -> bool {
+    let normalised = input.to_lowercase();
+    normalised == normalised.chars().rev().collect::<String>()
+}
+
+/// Case-insensitive comparison of alphanumeric characters only.
+fn is_loose_palindrome(input: &str) -> bool {
+    let alphanumeric = input
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<Vec<char>>();
+
+    alphanumeric.iter().eq(alphanumeric.iter().rev())
+}
+
+/// Compatibility-decomposes (NFKD) and lowercases the input, then compares grapheme
+/// clusters (recomposed to NFC) so combining marks stay attached to their base character.
+fn is_normalised_palindrome(input: &str) -> bool {
+    let decomposed = input.nfkd().collect::<String>().to_lowercase();
+    let graphemes = decomposed
+        .graphemes(true)
+        .map(|grapheme| grapheme.nfc().collect::<String>())
+        .collect::<Vec<String>>();
+
+    graphemes.iter().eq(graphemes.iter().rev())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_palindrome;
+    use crate::models::filters::PalindromeMode;
+
+    /// Strict, loose and normalised results for `input`.
+    fn modes(input: &str) -> [bool; 3] {
+        [
+            is_palindrome(input, PalindromeMode::Strict),
+            is_palindrome(input, PalindromeMode::Loose),
+            is_palindrome(input, PalindromeMode::Normalised),
+        ]
+    }
+
+    #[test]
+    fn case() {
+        assert_eq!(modes("Racecar"), [true, true, true]);
+        assert_eq!(modes("Été"), [true, true, true]);
+        assert_eq!(modes("hello"), [false, false, false]);
+    }
+
+    #[test]
+    fn punctuation_and_whitespace() {
+        assert_eq!(
+            modes("A man, a plan, a canal: Panama!"),
+            [false, true, false]
+        );
+        assert_eq!(modes("No 'x' in Nixon"), [false, true, false]);
+        assert_eq!(modes("never odd or even"), [false, true, false]);
+        assert_eq!(modes("ab, ba"), [false, true, false]);
+        assert_eq!(modes("12:321"), [false, true, false]);
+        assert_eq!(modes("hello, world"), [false, false, false]);
+    }
+
+    #[test]
+    fn combining_marks() {
+        // Precomposed "é" at the start, "e" plus a combining acute accent at the end.
+        assert_eq!(modes("\u{E9}t\u{65}\u{301}"), [false, false, true]);
+    }
+
+    #[test]
+    fn compatibility_characters() {
+        // The "ﬁ" ligature decomposes to "fi".
+        assert_eq!(modes("iffi"), [true, true, true]);
+        assert_eq!(modes("if\u{FB01}"), [false, false, true]);
+        assert_eq!(modes("ＡｂＡ"), [true, true, true]);
+    }
+
+    #[test]
+    fn emoji_sequences() {
+        assert_eq!(modes("👍🏽a👍🏽"), [false, true, true]);
+    }
+}
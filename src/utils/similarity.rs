@@ -0,0 +1,19 @@
+/// Levenshtein distance between `a` and `b`, counted in Unicode scalar values.
+pub fn edit_distance(a: &str, b: &str) -> i32 {
+    let b_chars = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b_chars.len()).collect::<Vec<usize>>();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()] as i32
+}